@@ -0,0 +1,11 @@
+//! Build script: compiles the gRPC protocol when the `grpc` feature is enabled
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available");
+        std::env::set_var("PROTOC", protoc);
+        tonic_prost_build::compile_protos("proto/bf2042_stats.proto")
+            .expect("Failed to compile proto/bf2042_stats.proto");
+    }
+}
@@ -0,0 +1,200 @@
+//! Ballistics calculations derived from configuration stats
+//!
+//! All functions are pure and operate on the raw values stored in the
+//! database (`velocity` in m/s, `range` in meters, RPM values, `Damage`).
+
+use crate::models::{Damage, DamageAtRange, Hitbox, HitboxBulletsToKill, TargetProfile};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// Health of a default (unarmored) soldier
+pub const DEFAULT_TARGET_HEALTH: i16 = 100;
+
+/// Gravitational acceleration in m/s² used for bullet drop
+pub const GRAVITY: f64 = 9.81;
+
+/// Sustained fire rate: automatic fire if the configuration has it, else single fire
+///
+/// Burst rates are left out on purpose. They are the cyclic rate within a
+/// burst, and without the pause between bursts they overstate how fast damage
+/// lands over a whole kill.
+pub fn fire_rate(rpm_single: Option<i16>, rpm_auto: Option<i16>) -> Option<i16> {
+    [rpm_auto, rpm_single]
+        .into_iter()
+        .flatten()
+        .find(|rpm| *rpm > 0)
+}
+
+/// Damage at `range` from a dropoff table
+///
+/// Uses the highest dropoff range at or below `range`, matching the
+/// step function of the SQL range queries.
+pub fn damage_at(dropoffs: impl IntoIterator<Item = (i16, Damage)>, range: i16) -> Option<Damage> {
+    dropoffs
+        .into_iter()
+        .filter(|(dropoff_range, _)| *dropoff_range <= range)
+        .max_by_key(|(dropoff_range, _)| *dropoff_range)
+        .map(|(_, damage)| damage)
+}
+
+/// Damage of one shot when every projectile hits
+///
+/// Shotgun damage is stored per pellet, so a shot deals `pellet_count` times
+/// the stored damage. Other weapons fire one projectile per shot.
+pub fn shot_damage(damage: Damage, pellet_count: Option<i16>) -> Damage {
+    damage * Decimal::from(pellet_count.unwrap_or(1).max(1))
+}
+
+/// Number of shots required to deal `health` damage
+///
+/// `damage` is per projectile; every pellet of a shot is assumed to hit.
+pub fn bullets_to_kill(damage: Damage, pellet_count: Option<i16>, health: Decimal) -> Option<u32> {
+    let damage = shot_damage(damage, pellet_count);
+    if damage <= Decimal::ZERO {
+        return None;
+    }
+
+    (health / damage).ceil().to_u32()
+}
+
+/// Time in milliseconds between the first and the last hit of a kill
+pub fn time_to_kill_ms(bullets_to_kill: u32, rpm: i16) -> Option<f64> {
+    if rpm <= 0 || bullets_to_kill == 0 {
+        return None;
+    }
+
+    Some(f64::from(bullets_to_kill - 1) * 60_000.0 / f64::from(rpm))
+}
+
+/// Projectile flight time in milliseconds to reach `range`
+pub fn flight_time_ms(range: i16, velocity: i16) -> Option<f64> {
+    if velocity <= 0 {
+        return None;
+    }
+
+    Some(f64::from(range) / f64::from(velocity) * 1000.0)
+}
+
+/// Vertical bullet drop in meters at `range`, ignoring drag
+pub fn bullet_drop(range: i16, velocity: i16) -> Option<f64> {
+    let seconds = flight_time_ms(range, velocity)? / 1000.0;
+    Some(0.5 * GRAVITY * seconds * seconds)
+}
+
+/// Distance in meters to lead a target crossing at `target_speed` m/s
+pub fn lead_distance(range: i16, velocity: i16, target_speed: f64) -> Option<f64> {
+    let seconds = flight_time_ms(range, velocity)? / 1000.0;
+    Some(target_speed * seconds)
+}
+
+/// Time to kill including projectile travel time to the target
+///
+/// The kill lands once the last projectile arrives, so the whole
+/// firing sequence is delayed by one flight time.
+pub fn effective_ttk_ms(
+    damage: Damage,
+    pellet_count: Option<i16>,
+    health: Decimal,
+    rpm: i16,
+    range: i16,
+    velocity: i16,
+) -> Option<f64> {
+    let ttk = time_to_kill_ms(bullets_to_kill(damage, pellet_count, health)?, rpm)?;
+    Some(ttk + flight_time_ms(range, velocity)?)
+}
+
+/// Fill the engagement columns of a `DamageAtRange` row
+///
+/// `target_range` is the actual distance to the target, not the dropoff
+/// bracket reported in `effective_range`.
+pub fn annotate(
+    mut row: DamageAtRange,
+    target_range: i16,
+    target_speed: f64,
+    target: &TargetProfile,
+) -> DamageAtRange {
+    let rpm = fire_rate(row.rpm_single, row.rpm_auto);
+    let health = target.effective_health();
+
+    row.flight_time_ms = flight_time_ms(target_range, row.velocity);
+    row.bullet_drop = bullet_drop(target_range, row.velocity);
+    row.lead_distance = lead_distance(target_range, row.velocity, target_speed);
+    row.effective_ttk_ms = rpm.and_then(|rpm| {
+        effective_ttk_ms(
+            row.damage,
+            row.pellet_count,
+            health,
+            rpm,
+            target_range,
+            row.velocity,
+        )
+    });
+    row
+}
+
+/// Hits to kill on each hitbox for a `DamageAtRange` row
+pub fn hitbox_bullets_to_kill(row: DamageAtRange, target: &TargetProfile) -> HitboxBulletsToKill {
+    let multipliers = row.hitbox_multipliers();
+    let health = target.effective_health();
+    let btk = |hitbox| {
+        bullets_to_kill(
+            row.damage * target.hit_multiplier(hitbox, &multipliers),
+            row.pellet_count,
+            health,
+        )
+    };
+
+    HitboxBulletsToKill {
+        head: btk(Hitbox::Head),
+        upper_chest: btk(Hitbox::UpperChest),
+        torso: btk(Hitbox::Torso),
+        limb: btk(Hitbox::Limb),
+        weapon_name: row.weapon_name,
+        barrel_name: row.barrel_name,
+        ammo_type_name: row.ammo_type_name,
+        effective_range: row.effective_range,
+        damage: row.damage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bullets_and_time_to_kill() {
+        // 4 hits at 25 damage, 3 intervals at 600 RPM
+        let btk = bullets_to_kill(Decimal::new(25, 0), None, Decimal::ONE_HUNDRED).unwrap();
+        assert_eq!(btk, 4);
+        assert_eq!(time_to_kill_ms(btk, 600), Some(300.0));
+
+        // 22.5 damage needs 5 hits
+        let damage = Decimal::new(225, 1);
+        assert_eq!(bullets_to_kill(damage, None, Decimal::ONE_HUNDRED), Some(5));
+
+        // Shotgun damage is per pellet: 11 pellets of 18 kill in one shot
+        let pellet = Decimal::new(18, 0);
+        assert_eq!(
+            bullets_to_kill(pellet, Some(11), Decimal::ONE_HUNDRED),
+            Some(1)
+        );
+        assert_eq!(shot_damage(pellet, Some(11)), Decimal::new(198, 0));
+    }
+
+    #[test]
+    fn test_projectile_flight() {
+        // 500m at 500m/s takes one second
+        assert_eq!(flight_time_ms(500, 500), Some(1000.0));
+        assert_eq!(lead_distance(500, 500, 6.0), Some(6.0));
+        assert_eq!(bullet_drop(500, 500), Some(GRAVITY / 2.0));
+        assert_eq!(flight_time_ms(100, 0), None);
+    }
+
+    #[test]
+    fn test_fire_rate() {
+        // Automatic fire wins over single fire; bursts are not a sustained rate
+        assert_eq!(fire_rate(Some(900), Some(600)), Some(600));
+        assert_eq!(fire_rate(Some(450), None), Some(450));
+        assert_eq!(fire_rate(None, None), None);
+    }
+}
@@ -0,0 +1,33 @@
+//! gRPC server for the Battlefield 2042 stats API
+//!
+//! Connects to the database given by `DatabaseConfig::from_env` and listens on
+//! `BIND_ADDR`, `127.0.0.1:50051` by default.
+
+use anyhow::Context;
+use bf2042_stats::{grpc, DatabaseConfig, StatsClient};
+use std::env;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::info;
+
+const DEFAULT_BIND_ADDR: &str = "127.0.0.1:50051";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let config = DatabaseConfig::from_env()?;
+    let client = StatsClient::new(&config).await?;
+
+    let addr: SocketAddr = env::var("BIND_ADDR")
+        .unwrap_or_else(|_| DEFAULT_BIND_ADDR.to_string())
+        .parse()
+        .context("BIND_ADDR must be a socket address")?;
+    info!("Listening on {}", addr);
+
+    tonic::transport::Server::builder()
+        .add_service(grpc::server(Arc::new(client)))
+        .serve(addr)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! HTTP server for the Battlefield 2042 stats API
+//!
+//! Connects to the database given by `DatabaseConfig::from_env` and listens on
+//! `BIND_ADDR`, `127.0.0.1:3000` by default.
+
+use anyhow::Context;
+use bf2042_stats::{http, DatabaseConfig, StatsClient};
+use std::env;
+use std::sync::Arc;
+use tracing::info;
+
+const DEFAULT_BIND_ADDR: &str = "127.0.0.1:3000";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let config = DatabaseConfig::from_env()?;
+    let client = StatsClient::new(&config).await?;
+
+    let addr = env::var("BIND_ADDR").unwrap_or_else(|_| DEFAULT_BIND_ADDR.to_string());
+    let listener = tokio::net::TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("Failed to bind {}", addr))?;
+    info!("Listening on {}", addr);
+
+    axum::serve(listener, http::router(Arc::new(client))).await?;
+    Ok(())
+}
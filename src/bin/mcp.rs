@@ -0,0 +1,55 @@
+//! MCP server for the Battlefield 2042 stats API
+//!
+//! Connects to the database given by `DatabaseConfig::from_env`.
+//! Serves a single session over stdio by default; with `--http` it serves many
+//! sessions over Streamable HTTP at `/mcp` on `BIND_ADDR`, `127.0.0.1:8000` by
+//! default, until interrupted.
+
+use anyhow::Context;
+use bf2042_stats::mcp::{self, StatsMcpServer};
+use bf2042_stats::{DatabaseConfig, StatsClient};
+use rmcp::ServiceExt;
+use std::env;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+const DEFAULT_BIND_ADDR: &str = "127.0.0.1:8000";
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    // stdout carries the protocol in stdio mode
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .init();
+
+    let config = DatabaseConfig::from_env()?;
+    let client = Arc::new(StatsClient::new(&config).await?);
+
+    if env::args().skip(1).any(|arg| arg == "--http") {
+        let addr = env::var("BIND_ADDR").unwrap_or_else(|_| DEFAULT_BIND_ADDR.to_string());
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .with_context(|| format!("Failed to bind {}", addr))?;
+        info!("Listening on {}", listener.local_addr()?);
+
+        let shutdown = CancellationToken::new();
+        tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move {
+                let _ = tokio::signal::ctrl_c().await;
+                info!("Shutting down");
+                shutdown.cancel();
+            }
+        });
+        axum::serve(listener, mcp::router(client, shutdown.clone()))
+            .with_graceful_shutdown(shutdown.cancelled_owned())
+            .await?;
+    } else {
+        let service = StatsMcpServer::new(client)
+            .serve(rmcp::transport::stdio())
+            .await?;
+        service.waiting().await?;
+    }
+    Ok(())
+}
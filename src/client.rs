@@ -1,19 +1,35 @@
 //! Stats client for querying weapon data
 
+use crate::ballistics;
+use crate::comparison::{self, AmmoComparison, Comparison};
 use crate::database::DatabaseManager;
+use crate::effects::{self, AttachmentEffect, ConfigDelta, RangeBand};
+use crate::loadout;
 use crate::models::{
-    BestConfigInCategory, DamageAtRange, DatabaseConfig, Weapon, WeaponAmmoStatsWithNames,
-    WeaponConfigWithDropoffs,
+    AmmoTypeWithCount, Attachment, BarrelWithCount, BestConfigInCategory, Category,
+    CategoryWithCount, ConfigQuery, ConfigSort, Cursor, Damage, DamageAtRange, DamageDropoff,
+    DamageTableRow, DatabaseConfig, Hitbox, HitboxBulletsToKill, Loadout, Page, RangeBandQuery,
+    RangeBandWinner, RankBy, SortOrder, TargetProfile, Weapon, WeaponAmmoStatsWithNames,
+    WeaponConfig, WeaponConfigWithDropoffs, WeaponDetails, WeaponMetrics, WeaponWithCategory,
+    WeaponWithConfigs,
 };
-use crate::Result;
-use futures::Stream;
-use futures::TryStreamExt;
+use crate::pareto::{self, ParetoConfig, ParetoMetric};
+use crate::simulation::{self, AccuracyProfile, Engagement, ExpectedTtk};
+use crate::{Result, StatsError};
+use futures::channel::mpsc;
+use futures::stream::BoxStream;
+use futures::{stream, SinkExt, Stream, StreamExt, TryStreamExt};
+use rust_decimal::Decimal;
+use std::sync::Arc;
 use tokio::sync::OnceCell;
 use tracing::{debug, info};
 
 // Global initialization state to ensure database is only initialized once
 static DB_INITIALIZATION: OnceCell<()> = OnceCell::const_new();
 
+/// Items buffered between a spawned query and a slow consumer
+const SPAWNED_STREAM_BUFFER: usize = 32;
+
 /// Ensure database exists
 async fn ensure_database_exists(db_manager: &DatabaseManager, database: &str) -> Result<()> {
     // Check if the database exists
@@ -44,17 +60,119 @@ async fn ensure_database_initialized(db_manager: &DatabaseManager) -> Result<()>
         )
         .fetch_one(db_manager.pool())
         .await?;
-            
+
+        // Tables and profiles added since the database was created are filled in either way
+        db_manager.migrate().await?;
+
         if !table_exists {
             info!("Database empty, initializing with embedded data");
-            db_manager.create_schema().await?;
             db_manager.populate_from_embedded_data().await?;
             info!("Database initialization completed");
         } else {
             debug!("Database already has data");
         }
         Ok::<(), crate::StatsError>(())
-    }).await.copied().map_err(Into::into)
+    }).await.copied()
+}
+
+/// Merge adjacent items of an ordered stream into groups as they arrive
+///
+/// `absorb` adds an item to the current group, or hands it back when it
+/// starts a new group, which is then created by `start`.
+fn group_adjacent<'a, T, G, S, A>(
+    items: impl Stream<Item = Result<T>> + Send + 'a,
+    start: S,
+    absorb: A,
+) -> impl Stream<Item = Result<G>> + Send + 'a
+where
+    T: Send + 'a,
+    G: Send + 'a,
+    S: Fn(T) -> G + Send + 'a,
+    A: Fn(&mut G, T) -> Option<T> + Send + 'a,
+{
+    let items = Box::pin(items).fuse();
+    let state = (items, None::<G>, start, absorb);
+    stream::unfold(state, |(mut items, mut pending, start, absorb)| async move {
+        loop {
+            match items.next().await {
+                Some(Ok(item)) => {
+                    let item = match pending.as_mut() {
+                        Some(group) => absorb(group, item),
+                        None => Some(item),
+                    };
+                    if let Some(item) = item {
+                        if let Some(done) = pending.replace(start(item)) {
+                            return Some((Ok(done), (items, pending, start, absorb)));
+                        }
+                    }
+                }
+                Some(Err(e)) => return Some((Err(e), (items, None, start, absorb))),
+                None => {
+                    return pending
+                        .take()
+                        .map(|group| (Ok(group), (items, None, start, absorb)))
+                }
+            }
+        }
+    })
+}
+
+/// Group configuration rows ordered by configuration into one `WeaponConfig` each
+fn group_config_rows<'a>(
+    rows: impl Stream<Item = Result<WeaponConfigWithDropoffs>> + Send + 'a,
+) -> impl Stream<Item = Result<WeaponConfig>> + Send + 'a {
+    group_adjacent(rows, WeaponConfig::from, |config: &mut WeaponConfig, row| {
+        if config.config_id != row.config_id {
+            return Some(row);
+        }
+        config.dropoffs.push(DamageDropoff {
+            range: row.range,
+            damage: row.damage,
+        });
+        None
+    })
+}
+
+/// Group configurations ordered by weapon into one `WeaponWithConfigs` each
+fn group_weapon_configs<'a>(
+    configs: impl Stream<Item = Result<WeaponConfig>> + Send + 'a,
+) -> impl Stream<Item = Result<WeaponWithConfigs>> + Send + 'a {
+    group_adjacent(
+        configs,
+        |config: WeaponConfig| WeaponWithConfigs {
+            weapon_name: config.weapon_name.clone(),
+            configurations: vec![config],
+        },
+        |weapon: &mut WeaponWithConfigs, config| {
+            if weapon.weapon_name != config.weapon_name {
+                return Some(config);
+            }
+            weapon.configurations.push(config);
+            None
+        },
+    )
+}
+
+/// Range bands for band-based analyses, rejecting unusable widths
+fn checked_range_bands(band_width: i16, open_band_start: i16) -> Result<Vec<RangeBand>> {
+    if band_width < 1 || open_band_start < 0 {
+        return Err(StatsError::QueryFailed(format!(
+            "Invalid range bands (width: {}, open from: {})",
+            band_width, open_band_start
+        )));
+    }
+    Ok(effects::range_bands(band_width, open_band_start))
+}
+
+/// Rows to fetch for a page of `limit` items; one extra row signals a next page
+fn page_fetch_limit(limit: i64) -> Result<i64> {
+    if limit < 1 {
+        return Err(StatsError::QueryFailed(format!(
+            "Page limit must be at least 1, got {}",
+            limit
+        )));
+    }
+    Ok(limit + 1)
 }
 
 pub struct StatsClient {
@@ -64,7 +182,7 @@ pub struct StatsClient {
 impl StatsClient {
     /// Create a new stats client with custom configuration
     pub async fn new(config: &DatabaseConfig) -> Result<Self> {
-        let database_name = config.url().split('/').last().unwrap_or("2042_stats");
+        let database_name = config.url().split('/').next_back().unwrap_or("2042_stats");
         // Administrative connection
 
 
@@ -85,6 +203,301 @@ impl StatsClient {
         Ok(Self { db_manager })
     }
 
+    /// Run a query stream in its own task, detached from the borrow of the client
+    ///
+    /// Client streams borrow the client, which servers cannot hold on to while
+    /// a response streams out. The query instead runs against the shared handle
+    /// and `args`, and its items are forwarded through a bounded channel, so a
+    /// slow consumer holds back the query. The query stops after its first
+    /// error or once the returned stream is dropped.
+    pub fn spawn_stream<A, T, F>(
+        self: &Arc<Self>,
+        args: A,
+        query: F,
+    ) -> impl Stream<Item = Result<T>> + Send + 'static
+    where
+        A: Send + Sync + 'static,
+        T: Send + 'static,
+        F: for<'a> FnOnce(&'a StatsClient, &'a A) -> BoxStream<'a, Result<T>> + Send + 'static,
+    {
+        let (mut tx, rx) = mpsc::channel(SPAWNED_STREAM_BUFFER);
+        let client = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut items = query(&client, &args);
+            while let Some(item) = items.next().await {
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+
+        rx
+    }
+
+    /// Get all categories with their weapon counts
+    pub fn categories(&self) -> impl Stream<Item = Result<CategoryWithCount>> + '_ {
+        debug!("Starting streaming query for categories");
+
+        self.categories_after(None, None)
+    }
+
+    /// Get a page of categories with their weapon counts
+    pub async fn categories_page(
+        &self,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<CategoryWithCount>> {
+        debug!(
+            "Querying page of categories (limit: {}, cursor: {:?})",
+            limit, cursor
+        );
+
+        let after: Option<String> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .categories_after(after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |category: &CategoryWithCount| {
+            category.category_name.clone()
+        }))
+    }
+
+    /// Categories ordered by name, starting after the category named `after`
+    fn categories_after(
+        &self,
+        after: Option<String>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<CategoryWithCount>> + '_ {
+        sqlx::query_as!(
+            CategoryWithCount,
+            r#"
+            SELECT
+                c.category_id,
+                c.category_name,
+                COUNT(w.weapon_id) as "weapon_count!"
+            FROM categories c
+            LEFT JOIN weapons w ON c.category_id = w.category_id
+            WHERE ($1::TEXT IS NULL OR c.category_name > $1)
+            GROUP BY c.category_id, c.category_name
+            ORDER BY c.category_name
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get all barrels with the number of configurations using them
+    pub fn barrels(&self) -> impl Stream<Item = Result<BarrelWithCount>> + '_ {
+        debug!("Starting streaming query for barrels");
+
+        self.barrels_after(None, None)
+    }
+
+    /// Get a page of barrels with the number of configurations using them
+    pub async fn barrels_page(
+        &self,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<BarrelWithCount>> {
+        debug!(
+            "Querying page of barrels (limit: {}, cursor: {:?})",
+            limit, cursor
+        );
+
+        let after: Option<String> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .barrels_after(after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |barrel: &BarrelWithCount| {
+            barrel.barrel_name.clone()
+        }))
+    }
+
+    /// Barrels ordered by name, starting after the barrel named `after`
+    fn barrels_after(
+        &self,
+        after: Option<String>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<BarrelWithCount>> + '_ {
+        sqlx::query_as!(
+            BarrelWithCount,
+            r#"
+            SELECT
+                b.barrel_id,
+                b.barrel_name,
+                COUNT(c.config_id) as "config_count!"
+            FROM barrels b
+            LEFT JOIN configurations c ON b.barrel_id = c.barrel_id
+            WHERE ($1::TEXT IS NULL OR b.barrel_name > $1)
+            GROUP BY b.barrel_id, b.barrel_name
+            ORDER BY b.barrel_name
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get all ammo types with the number of configurations using them
+    pub fn ammo_types(&self) -> impl Stream<Item = Result<AmmoTypeWithCount>> + '_ {
+        debug!("Starting streaming query for ammo types");
+
+        self.ammo_types_after(None, None)
+    }
+
+    /// Get a page of ammo types with the number of configurations using them
+    pub async fn ammo_types_page(
+        &self,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<AmmoTypeWithCount>> {
+        debug!(
+            "Querying page of ammo types (limit: {}, cursor: {:?})",
+            limit, cursor
+        );
+
+        let after: Option<String> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .ammo_types_after(after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |ammo: &AmmoTypeWithCount| {
+            ammo.ammo_type_name.clone()
+        }))
+    }
+
+    /// Ammo types ordered by name, starting after the ammo type named `after`
+    fn ammo_types_after(
+        &self,
+        after: Option<String>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<AmmoTypeWithCount>> + '_ {
+        sqlx::query_as!(
+            AmmoTypeWithCount,
+            r#"
+            SELECT
+                a.ammo_id,
+                a.ammo_type_name,
+                COUNT(c.config_id) as "config_count!"
+            FROM ammo_types a
+            LEFT JOIN configurations c ON a.ammo_id = c.ammo_id
+            WHERE ($1::TEXT IS NULL OR a.ammo_type_name > $1)
+            GROUP BY a.ammo_id, a.ammo_type_name
+            ORDER BY a.ammo_type_name
+            LIMIT $2
+            "#,
+            after,
+            limit
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get all weapons with their category and configuration counts
+    pub fn all_weapons(&self) -> impl Stream<Item = Result<WeaponWithCategory>> + '_ {
+        debug!("Starting streaming query for all weapons");
+
+        self.all_weapons_after(None, None)
+    }
+
+    /// Get a page of weapons with their category and configuration counts
+    pub async fn all_weapons_page(
+        &self,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<WeaponWithCategory>> {
+        debug!(
+            "Querying page of all weapons (limit: {}, cursor: {:?})",
+            limit, cursor
+        );
+
+        let after: Option<(String, String)> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .all_weapons_after(after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |weapon: &WeaponWithCategory| {
+            (weapon.category_name.clone(), weapon.weapon_name.clone())
+        }))
+    }
+
+    /// Weapons ordered by category and name, starting after the `(category, weapon)` key
+    fn all_weapons_after(
+        &self,
+        after: Option<(String, String)>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<WeaponWithCategory>> + '_ {
+        let (after_category, after_weapon) = after.unzip();
+
+        sqlx::query_as!(
+            WeaponWithCategory,
+            r#"
+            SELECT
+                w.weapon_id,
+                w.weapon_name,
+                w.category_id,
+                cat.category_name,
+                COUNT(c.config_id) as "config_count!"
+            FROM weapons w
+            JOIN categories cat ON w.category_id = cat.category_id
+            LEFT JOIN configurations c ON w.weapon_id = c.weapon_id
+            WHERE ($1::TEXT IS NULL OR (cat.category_name, w.weapon_name) > ($1, $2::TEXT))
+            GROUP BY w.weapon_id, w.weapon_name, w.category_id, cat.category_name
+            ORDER BY cat.category_name, w.weapon_name
+            LIMIT $3
+            "#,
+            after_category,
+            after_weapon,
+            limit
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get a category by name
+    pub async fn category(&self, category_name: &str) -> Result<Category> {
+        debug!("Querying category: {}", category_name);
+
+        sqlx::query_as!(
+            Category,
+            r#"
+            SELECT category_id, category_name
+            FROM categories
+            WHERE category_name = $1
+            "#,
+            category_name
+        )
+        .fetch_optional(self.db_manager.pool())
+        .await?
+        .ok_or_else(|| StatsError::NotFound(format!("Category '{}'", category_name)))
+    }
+
+    /// Get a weapon by name
+    pub async fn weapon(&self, weapon_name: &str) -> Result<Weapon> {
+        debug!("Querying weapon: {}", weapon_name);
+
+        sqlx::query_as!(
+            Weapon,
+            r#"
+            SELECT w.weapon_id, w.weapon_name, w.category_id
+            FROM weapons w
+            WHERE w.weapon_name = $1
+            "#,
+            weapon_name
+        )
+        .fetch_optional(self.db_manager.pool())
+        .await?
+        .ok_or_else(|| StatsError::NotFound(format!("Weapon '{}'", weapon_name)))
+    }
+
     /// Get weapons by category
     pub fn weapons_by_category(
         &self,
@@ -95,6 +508,38 @@ impl StatsClient {
             category_name
         );
 
+        self.weapons_by_category_after(category_name, None, None)
+    }
+
+    /// Get a page of weapons by category
+    pub async fn weapons_by_category_page(
+        &self,
+        category_name: &str,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<Weapon>> {
+        debug!(
+            "Querying page of weapons by category: {} (limit: {}, cursor: {:?})",
+            category_name, limit, cursor
+        );
+
+        let after: Option<String> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .weapons_by_category_after(category_name, after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |weapon: &Weapon| {
+            weapon.weapon_name.clone()
+        }))
+    }
+
+    /// Weapons by category ordered by name, starting after the weapon named `after`
+    fn weapons_by_category_after(
+        &self,
+        category_name: &str,
+        after: Option<String>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<Weapon>> + '_ {
         sqlx::query_as!(
             Weapon,
             r#"
@@ -102,9 +547,13 @@ impl StatsClient {
             FROM weapons w
             JOIN categories c ON w.category_id = c.category_id
             WHERE c.category_name = $1
+              AND ($2::TEXT IS NULL OR w.weapon_name > $2)
             ORDER BY w.weapon_name
+            LIMIT $3
             "#,
-            category_name
+            category_name,
+            after,
+            limit
         )
         .fetch(self.db_manager.pool())
         .map_err(|e| e.into())
@@ -120,6 +569,43 @@ impl StatsClient {
             weapon_name
         );
 
+        self.weapon_configs_after(weapon_name, None, None)
+    }
+
+    /// Get a page of weapon configuration dropoff rows
+    pub async fn weapon_configs_page(
+        &self,
+        weapon_name: &str,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<WeaponConfigWithDropoffs>> {
+        debug!(
+            "Querying page of weapon configurations: {} (limit: {}, cursor: {:?})",
+            weapon_name, limit, cursor
+        );
+
+        let after: Option<(String, String, i16)> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .weapon_configs_after(weapon_name, after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |row: &WeaponConfigWithDropoffs| {
+            (row.barrel_name.clone(), row.ammo_type_name.clone(), row.range)
+        }))
+    }
+
+    /// Weapon configuration rows ordered by barrel, ammo and range, starting after `after`
+    fn weapon_configs_after(
+        &self,
+        weapon_name: &str,
+        after: Option<(String, String, i16)>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<WeaponConfigWithDropoffs>> + '_ {
+        let (after_barrel, after_ammo, after_range) = match after {
+            Some((barrel, ammo, range)) => (Some(barrel), Some(ammo), Some(range)),
+            None => (None, None, None),
+        };
+
         sqlx::query_as!(
             WeaponConfigWithDropoffs,
             r#"
@@ -132,6 +618,12 @@ impl StatsClient {
                 c.rpm_single,
                 c.rpm_burst,
                 c.rpm_auto,
+                c.recoil_vertical,
+                c.recoil_horizontal,
+                c.recoil_first_shot_multiplier,
+                c.spread_ads,
+                c.spread_hipfire,
+                c.spread_bloom_per_shot,
                 cd.range,
                 cd.damage
             FROM weapons w
@@ -140,9 +632,240 @@ impl StatsClient {
             JOIN barrels b ON c.barrel_id = b.barrel_id
             JOIN ammo_types a ON c.ammo_id = a.ammo_id
             WHERE w.weapon_name = $1
+              AND ($2::TEXT IS NULL
+                OR (b.barrel_name, a.ammo_type_name, cd.range) > ($2, $3::TEXT, $4::SMALLINT))
             ORDER BY b.barrel_name, a.ammo_type_name, cd.range
+            LIMIT $5
+            "#,
+            weapon_name,
+            after_barrel,
+            after_ammo,
+            after_range,
+            limit
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get weapon configurations with their dropoffs and ammo stats, one item per configuration
+    ///
+    /// Rows of `weapon_configs` are grouped as they arrive, so only the
+    /// configuration being assembled is held in memory.
+    pub fn weapon_configs_grouped<'a>(
+        &'a self,
+        weapon_name: &'a str,
+    ) -> impl Stream<Item = Result<WeaponConfig>> + 'a {
+        debug!(
+            "Starting grouped streaming query for weapon configurations: {}",
+            weapon_name
+        );
+
+        stream::once(async move {
+            let ammo_stats: Vec<WeaponAmmoStatsWithNames> =
+                self.weapon_ammo_stats(weapon_name).try_collect().await?;
+
+            Ok::<_, StatsError>(group_config_rows(self.weapon_configs(weapon_name)).map_ok(
+                move |mut config| {
+                    config.ammo_stats = ammo_stats
+                        .iter()
+                        .find(|stats| stats.ammo_type_name == config.ammo_type_name)
+                        .cloned();
+                    config
+                },
+            ))
+        })
+        .try_flatten()
+    }
+
+    /// Get the weapons that have a barrel, with their configurations using it
+    ///
+    /// Configurations are returned without ammo stats.
+    pub fn weapons_with_barrel<'a>(
+        &'a self,
+        barrel_name: &'a str,
+    ) -> impl Stream<Item = Result<WeaponWithConfigs>> + 'a {
+        debug!("Starting streaming query for weapons with barrel: {}", barrel_name);
+
+        group_weapon_configs(group_config_rows(
+            self.configs_matching(Some(barrel_name), None),
+        ))
+    }
+
+    /// Get the weapons that have an ammo type, with their configurations using it
+    ///
+    /// Configurations are returned without ammo stats.
+    pub fn weapons_with_ammo<'a>(
+        &'a self,
+        ammo_type_name: &'a str,
+    ) -> impl Stream<Item = Result<WeaponWithConfigs>> + 'a {
+        debug!("Starting streaming query for weapons with ammo: {}", ammo_type_name);
+
+        group_weapon_configs(group_config_rows(
+            self.configs_matching(None, Some(vec![ammo_type_name.to_string()])),
+        ))
+    }
+
+    /// Compare two ammo types on every weapon and barrel that has both
+    pub fn ammo_comparison<'a>(
+        &'a self,
+        ammo_a: &'a str,
+        ammo_b: &'a str,
+    ) -> impl Stream<Item = Result<AmmoComparison>> + 'a {
+        debug!("Starting streaming query for ammo comparison: {} vs {}", ammo_a, ammo_b);
+
+        let ammo_type_names = vec![ammo_a.to_string(), ammo_b.to_string()];
+        let rows = self.configs_matching(None, Some(ammo_type_names));
+        group_adjacent(
+            group_config_rows(rows),
+            |config| vec![config],
+            |configs: &mut Vec<WeaponConfig>, config| {
+                let first = &configs[0];
+                if (&first.weapon_name, &first.barrel_name)
+                    != (&config.weapon_name, &config.barrel_name)
+                {
+                    return Some(config);
+                }
+                configs.push(config);
+                None
+            },
+        )
+        .try_filter_map(move |configs| async move {
+            let find = |ammo: &str| configs.iter().find(|config| config.ammo_type_name == ammo);
+            Ok(find(ammo_a)
+                .zip(find(ammo_b))
+                .map(|(a, b)| comparison::compare_ammo(a, b)))
+        })
+    }
+
+    /// Get the change of every non-Factory barrel and non-Standard ammo configuration
+    /// of a weapon against its baseline configuration
+    pub async fn attachment_deltas(
+        &self,
+        weapon_name: &str,
+        band_width: i16,
+        open_band_start: i16,
+        target: &TargetProfile,
+    ) -> Result<Vec<ConfigDelta>> {
+        debug!(
+            "Computing attachment deltas for weapon: {} (width: {}, open from: {}, target: {})",
+            weapon_name, band_width, open_band_start, target.profile_name
+        );
+
+        let bands = checked_range_bands(band_width, open_band_start)?;
+        let configs: Vec<WeaponConfig> =
+            self.weapon_configs_grouped(weapon_name).try_collect().await?;
+
+        if configs.is_empty() {
+            return Err(StatsError::NotFound(format!("Weapon '{}'", weapon_name)));
+        }
+
+        Ok(effects::weapon_deltas(&configs, &bands, target))
+    }
+
+    /// Get the average effect of every barrel and ammo type across the arsenal
+    pub async fn attachment_effects(
+        &self,
+        band_width: i16,
+        open_band_start: i16,
+        target: &TargetProfile,
+    ) -> Result<Vec<AttachmentEffect>> {
+        debug!(
+            "Computing attachment effects (width: {}, open from: {}, target: {})",
+            band_width, open_band_start, target.profile_name
+        );
+
+        let bands = checked_range_bands(band_width, open_band_start)?;
+        let deltas = group_weapon_configs(group_config_rows(self.configs_matching(None, None)))
+            .try_fold(Vec::new(), |mut deltas, weapon| {
+                deltas.extend(effects::weapon_deltas(&weapon.configurations, &bands, target));
+                async move { Ok(deltas) }
+            })
+            .await?;
+
+        Ok(effects::aggregate(&deltas, &bands))
+    }
+
+    /// Configuration rows with a barrel and one of the ammo types, ordered by weapon
+    fn configs_matching(
+        &self,
+        barrel_name: Option<&str>,
+        ammo_type_names: Option<Vec<String>>,
+    ) -> impl Stream<Item = Result<WeaponConfigWithDropoffs>> + '_ {
+        sqlx::query_as!(
+            WeaponConfigWithDropoffs,
+            r#"
+            SELECT
+                c.config_id,
+                w.weapon_name,
+                b.barrel_name,
+                a.ammo_type_name,
+                c.velocity,
+                c.rpm_single,
+                c.rpm_burst,
+                c.rpm_auto,
+                c.recoil_vertical,
+                c.recoil_horizontal,
+                c.recoil_first_shot_multiplier,
+                c.spread_ads,
+                c.spread_hipfire,
+                c.spread_bloom_per_shot,
+                cd.range,
+                cd.damage
+            FROM weapons w
+            JOIN configurations c ON w.weapon_id = c.weapon_id
+            JOIN config_dropoffs cd ON c.config_id = cd.config_id
+            JOIN barrels b ON c.barrel_id = b.barrel_id
+            JOIN ammo_types a ON c.ammo_id = a.ammo_id
+            WHERE ($1::TEXT IS NULL OR b.barrel_name = $1)
+              AND ($2::TEXT[] IS NULL OR a.ammo_type_name = ANY($2))
+            ORDER BY w.weapon_name, b.barrel_name, a.ammo_type_name, cd.range
+            "#,
+            barrel_name,
+            ammo_type_names.as_deref()
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get a single configuration with its damage dropoffs
+    pub fn config_dropoffs(
+        &self,
+        config_id: i32,
+    ) -> impl Stream<Item = Result<WeaponConfigWithDropoffs>> + '_ {
+        debug!(
+            "Starting streaming query for configuration dropoffs: {}",
+            config_id
+        );
+
+        sqlx::query_as!(
+            WeaponConfigWithDropoffs,
+            r#"
+            SELECT
+                c.config_id,
+                w.weapon_name,
+                b.barrel_name,
+                a.ammo_type_name,
+                c.velocity,
+                c.rpm_single,
+                c.rpm_burst,
+                c.rpm_auto,
+                c.recoil_vertical,
+                c.recoil_horizontal,
+                c.recoil_first_shot_multiplier,
+                c.spread_ads,
+                c.spread_hipfire,
+                c.spread_bloom_per_shot,
+                cd.range,
+                cd.damage
+            FROM configurations c
+            JOIN weapons w ON c.weapon_id = w.weapon_id
+            JOIN config_dropoffs cd ON c.config_id = cd.config_id
+            JOIN barrels b ON c.barrel_id = b.barrel_id
+            JOIN ammo_types a ON c.ammo_id = a.ammo_id
+            WHERE c.config_id = $1
+            ORDER BY cd.range
             "#,
-            weapon_name
+            config_id
         )
         .fetch(self.db_manager.pool())
         .map_err(|e| e.into())
@@ -158,6 +881,38 @@ impl StatsClient {
             weapon_name
         );
 
+        self.weapon_ammo_stats_after(weapon_name, None, None)
+    }
+
+    /// Get a page of weapon ammo stats
+    pub async fn weapon_ammo_stats_page(
+        &self,
+        weapon_name: &str,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<WeaponAmmoStatsWithNames>> {
+        debug!(
+            "Querying page of weapon ammo stats: {} (limit: {}, cursor: {:?})",
+            weapon_name, limit, cursor
+        );
+
+        let after: Option<String> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .weapon_ammo_stats_after(weapon_name, after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |stats: &WeaponAmmoStatsWithNames| {
+            stats.ammo_type_name.clone()
+        }))
+    }
+
+    /// Weapon ammo stats ordered by ammo type, starting after the ammo type named `after`
+    fn weapon_ammo_stats_after(
+        &self,
+        weapon_name: &str,
+        after: Option<String>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<WeaponAmmoStatsWithNames>> + '_ {
         sqlx::query_as!(
             WeaponAmmoStatsWithNames,
             r#"
@@ -168,14 +923,20 @@ impl StatsClient {
                 was.empty_reload_time,
                 was.tactical_reload_time,
                 was.headshot_multiplier,
-                was.pellet_count
+                was.pellet_count,
+                was.upper_chest_multiplier,
+                was.limb_multiplier
             FROM weapon_ammo_stats was
             JOIN weapons w ON was.weapon_id = w.weapon_id
             JOIN ammo_types a ON was.ammo_id = a.ammo_id
             WHERE w.weapon_name = $1
+              AND ($2::TEXT IS NULL OR a.ammo_type_name > $2)
             ORDER BY a.ammo_type_name
+            LIMIT $3
             "#,
-            weapon_name
+            weapon_name,
+            after,
+            limit
         )
         .fetch(self.db_manager.pool())
         .map_err(|e| e.into())
@@ -192,6 +953,45 @@ impl StatsClient {
             target_range, weapon_name
         );
 
+        self.damage_at_range_after(weapon_name, target_range, None, None)
+    }
+
+    /// Get a page of effective damage rows at specific range
+    pub async fn damage_at_range_page(
+        &self,
+        weapon_name: &str,
+        target_range: i16,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<DamageAtRange>> {
+        debug!(
+            "Querying page of damage at range {} for weapon: {} (limit: {}, cursor: {:?})",
+            target_range, weapon_name, limit, cursor
+        );
+
+        let after: Option<(Damage, String, String)> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .damage_at_range_after(weapon_name, target_range, after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |row: &DamageAtRange| {
+            (row.damage, row.barrel_name.clone(), row.ammo_type_name.clone())
+        }))
+    }
+
+    /// Damage at range ordered by damage (highest first), barrel and ammo, starting after `after`
+    fn damage_at_range_after(
+        &self,
+        weapon_name: &str,
+        target_range: i16,
+        after: Option<(Damage, String, String)>,
+        limit: Option<i64>,
+    ) -> impl Stream<Item = Result<DamageAtRange>> + '_ {
+        let (after_damage, after_barrel, after_ammo) = match after {
+            Some((damage, barrel, ammo)) => (Some(damage), Some(barrel), Some(ammo)),
+            None => (None, None, None),
+        };
+
         let weapon_name = weapon_name.to_string();
         sqlx::query_as!(
             DamageAtRange,
@@ -219,22 +1019,74 @@ impl StatsClient {
                 c.velocity,
                 c.rpm_single,
                 c.rpm_burst,
-                c.rpm_auto
+                c.rpm_auto,
+                was.headshot_multiplier as "headshot_multiplier?",
+                was.upper_chest_multiplier,
+                was.limb_multiplier,
+                was.pellet_count,
+                NULL::FLOAT8 as flight_time_ms,
+                NULL::FLOAT8 as bullet_drop,
+                NULL::FLOAT8 as lead_distance,
+                NULL::FLOAT8 as effective_ttk_ms
             FROM weapons w
             JOIN configurations c ON w.weapon_id = c.weapon_id
             JOIN effective_damage ed ON c.config_id = ed.config_id AND ed.rn = 1
             JOIN barrels b ON c.barrel_id = b.barrel_id
             JOIN ammo_types a ON c.ammo_id = a.ammo_id
+            LEFT JOIN weapon_ammo_stats was ON (w.weapon_id = was.weapon_id AND a.ammo_id = was.ammo_id)
             WHERE w.weapon_name = $1
-            ORDER BY ed.damage DESC
+              AND ($3::NUMERIC IS NULL
+                OR ed.damage < $3
+                OR (ed.damage = $3 AND (b.barrel_name, a.ammo_type_name) > ($4::TEXT, $5::TEXT)))
+            ORDER BY ed.damage DESC, b.barrel_name, a.ammo_type_name
+            LIMIT $6
             "#,
             weapon_name,
-            target_range
+            target_range,
+            after_damage,
+            after_barrel,
+            after_ammo,
+            limit
         )
         .fetch(self.db_manager.pool())
         .map_err(|e| e.into())
     }
 
+    /// Get damage at range with projectile flight time, bullet drop, lead
+    /// distance and travel-time-inclusive TTK for a target moving at
+    /// `target_speed` m/s
+    pub fn engagement_at_range<'a>(
+        &'a self,
+        weapon_name: &str,
+        target_range: i16,
+        target_speed: f64,
+        target: &'a TargetProfile,
+    ) -> impl Stream<Item = Result<DamageAtRange>> + 'a {
+        debug!(
+            "Starting streaming query for engagement at range {} for weapon: {} (target speed: {}, target: {})",
+            target_range, weapon_name, target_speed, target.profile_name
+        );
+
+        self.damage_at_range(weapon_name, target_range)
+            .map_ok(move |row| ballistics::annotate(row, target_range, target_speed, target))
+    }
+
+    /// Get hits to kill per hitbox for weapon configurations at specific range
+    pub fn hitbox_bullets_to_kill<'a>(
+        &'a self,
+        weapon_name: &str,
+        target_range: i16,
+        target: &'a TargetProfile,
+    ) -> impl Stream<Item = Result<HitboxBulletsToKill>> + 'a {
+        debug!(
+            "Starting streaming query for hitbox bullets to kill at range {} for weapon: {} (target: {})",
+            target_range, weapon_name, target.profile_name
+        );
+
+        self.damage_at_range(weapon_name, target_range)
+            .map_ok(move |row| ballistics::hitbox_bullets_to_kill(row, target))
+    }
+
     /// Get top performing configurations in a category at specific range
     pub fn best_configs_in_category(
         &self,
@@ -247,7 +1099,80 @@ impl StatsClient {
             category_name, target_range, limit
         );
 
-        let category_name = category_name.to_string();
+        let query = ConfigQuery::new()
+            .with_category(category_name)
+            .at_range(target_range)
+            .sort_by(ConfigSort::Damage, SortOrder::Desc)
+            .with_limit(limit);
+        self.query_configs(&query)
+    }
+
+    /// Get a page of top performing configurations in a category at specific range
+    pub async fn best_configs_in_category_page(
+        &self,
+        category_name: &str,
+        target_range: i16,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<BestConfigInCategory>> {
+        let query = ConfigQuery::new()
+            .with_category(category_name)
+            .at_range(target_range)
+            .sort_by(ConfigSort::Damage, SortOrder::Desc);
+        self.query_configs_page(&query, limit, cursor).await
+    }
+
+    /// Get configurations matching a `ConfigQuery`
+    ///
+    /// Unset filters match everything, so a single parameterized statement
+    /// serves every combination of filters. Configurations without ammo stats
+    /// are left out, as their magazine and reload cannot be reported.
+    pub fn query_configs(
+        &self,
+        query: &ConfigQuery,
+    ) -> impl Stream<Item = Result<BestConfigInCategory>> + '_ {
+        debug!("Starting streaming query for configurations: {:?}", query);
+
+        self.query_configs_after(query, None)
+    }
+
+    /// Get a page of configurations matching a `ConfigQuery`
+    ///
+    /// The query's own limit and offset are replaced by the page window.
+    pub async fn query_configs_page(
+        &self,
+        query: &ConfigQuery,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<BestConfigInCategory>> {
+        debug!(
+            "Querying page of configurations: {:?} (limit: {}, cursor: {:?})",
+            query, limit, cursor
+        );
+
+        let after: Option<(Option<Decimal>, i32)> = cursor.map(Cursor::decode).transpose()?;
+        let window = ConfigQuery {
+            limit: Some(page_fetch_limit(limit)?),
+            offset: 0,
+            ..query.clone()
+        };
+        let items = self.query_configs_after(&window, after).try_collect().await?;
+        Ok(Page::from_items(items, limit, |row: &BestConfigInCategory| {
+            (query.sort_value(row), row.config_id)
+        }))
+    }
+
+    /// Configurations matching a query, starting after the `(sort value, config_id)` key `after`
+    fn query_configs_after(
+        &self,
+        query: &ConfigQuery,
+        after: Option<(Option<Decimal>, i32)>,
+    ) -> impl Stream<Item = Result<BestConfigInCategory>> + '_ {
+        let (after_value, after_config) = match after {
+            Some((value, config_id)) => (value, Some(config_id)),
+            None => (None, None),
+        };
+
         sqlx::query_as!(
             BestConfigInCategory,
             r#"
@@ -262,9 +1187,11 @@ impl StatsClient {
                     ) as rn
                 FROM configurations c
                 JOIN config_dropoffs cd ON c.config_id = cd.config_id
-                WHERE cd.range <= $2
+                WHERE cd.range <= $1
             )
             SELECT
+                c.config_id,
+                cat.category_name,
                 w.weapon_name,
                 b.barrel_name,
                 a.ammo_type_name,
@@ -277,26 +1204,530 @@ impl StatsClient {
                 was.magazine_size,
                 was.empty_reload_time,
                 was.tactical_reload_time,
-                was.headshot_multiplier
+                was.headshot_multiplier,
+                c.recoil_vertical,
+                c.recoil_horizontal,
+                c.recoil_first_shot_multiplier,
+                c.spread_ads,
+                c.spread_hipfire,
+                c.spread_bloom_per_shot
             FROM weapons w
             JOIN categories cat ON w.category_id = cat.category_id
             JOIN configurations c ON w.weapon_id = c.weapon_id
             JOIN effective_damage ed ON c.config_id = ed.config_id AND ed.rn = 1
             JOIN barrels b ON c.barrel_id = b.barrel_id
             JOIN ammo_types a ON c.ammo_id = a.ammo_id
-            LEFT JOIN weapon_ammo_stats was ON (w.weapon_id = was.weapon_id AND a.ammo_id = was.ammo_id)
-            WHERE cat.category_name = $1
-            ORDER BY ed.damage DESC
-            LIMIT $3
+            JOIN weapon_ammo_stats was ON (w.weapon_id = was.weapon_id AND a.ammo_id = was.ammo_id)
+            CROSS JOIN LATERAL (
+                SELECT CASE $15::TEXT
+                    WHEN 'damage' THEN ed.damage
+                    WHEN 'velocity' THEN c.velocity
+                    WHEN 'fire_rate' THEN COALESCE(NULLIF(c.rpm_auto, 0), NULLIF(c.rpm_single, 0))
+                    WHEN 'magazine_size' THEN was.magazine_size
+                    WHEN 'reload_time' THEN COALESCE(was.tactical_reload_time, was.empty_reload_time)
+                END * CASE WHEN $16::BOOL THEN -1 ELSE 1 END as sort_value
+            ) sv
+            WHERE ($2::TEXT IS NULL OR cat.category_name = $2)
+              AND ($3::TEXT IS NULL OR w.weapon_name = $3)
+              AND ($4::TEXT IS NULL OR b.barrel_name = $4)
+              AND ($5::TEXT IS NULL OR a.ammo_type_name = $5)
+              AND ($6::SMALLINT IS NULL OR c.velocity >= $6)
+              AND ($7::SMALLINT IS NULL OR c.velocity <= $7)
+              AND ($8::SMALLINT IS NULL OR COALESCE(NULLIF(c.rpm_auto, 0), NULLIF(c.rpm_single, 0)) >= $8)
+              AND ($9::SMALLINT IS NULL OR COALESCE(NULLIF(c.rpm_auto, 0), NULLIF(c.rpm_single, 0)) <= $9)
+              AND ($10::SMALLINT IS NULL OR was.magazine_size >= $10)
+              AND ($11::SMALLINT IS NULL OR was.magazine_size <= $11)
+              AND ($12::NUMERIC IS NULL OR COALESCE(was.tactical_reload_time, was.empty_reload_time) <= $12)
+              AND ($13::NUMERIC IS NULL OR ed.damage >= $13)
+              AND ($14::NUMERIC IS NULL OR ed.damage <= $14)
+              AND ($19::INT4 IS NULL OR CASE
+                    WHEN $20::NUMERIC IS NULL THEN sv.sort_value IS NULL AND c.config_id > $19
+                    ELSE sv.sort_value > $20
+                        OR (sv.sort_value = $20 AND c.config_id > $19)
+                        OR sv.sort_value IS NULL
+                  END)
+            ORDER BY sv.sort_value NULLS LAST, c.config_id
+            LIMIT $17
+            OFFSET $18
             "#,
-            category_name,
-            target_range,
-            limit as i64
+            query.range,
+            query.category,
+            query.weapon,
+            query.barrel,
+            query.ammo,
+            query.min_velocity,
+            query.max_velocity,
+            query.min_rpm,
+            query.max_rpm,
+            query.min_magazine,
+            query.max_magazine,
+            query.max_reload,
+            query.min_damage,
+            query.max_damage,
+            query.sort.as_str(),
+            query.order == SortOrder::Desc,
+            query.limit,
+            query.offset,
+            after_config,
+            after_value
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get every dropoff of every configuration as a flat damage table
+    ///
+    /// Uses the joins of `query_configs`, one row per dropoff instead of one per
+    /// configuration, ordered by category, weapon, configuration and range.
+    pub fn damage_table(&self) -> impl Stream<Item = Result<DamageTableRow>> + '_ {
+        debug!("Starting streaming query for the damage table");
+
+        sqlx::query_as!(
+            DamageTableRow,
+            r#"
+            SELECT
+                c.config_id,
+                w.weapon_name,
+                cat.category_name,
+                b.barrel_name,
+                a.ammo_type_name,
+                cd.range,
+                cd.damage,
+                c.velocity,
+                c.rpm_single,
+                c.rpm_burst,
+                c.rpm_auto,
+                was.magazine_size,
+                was.empty_reload_time,
+                was.tactical_reload_time,
+                was.headshot_multiplier,
+                was.pellet_count
+            FROM weapons w
+            JOIN categories cat ON w.category_id = cat.category_id
+            JOIN configurations c ON w.weapon_id = c.weapon_id
+            JOIN config_dropoffs cd ON c.config_id = cd.config_id
+            JOIN barrels b ON c.barrel_id = b.barrel_id
+            JOIN ammo_types a ON c.ammo_id = a.ammo_id
+            JOIN weapon_ammo_stats was ON (w.weapon_id = was.weapon_id AND a.ammo_id = was.ammo_id)
+            ORDER BY cat.category_name, w.weapon_name, c.config_id, cd.range
+            "#
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get the top configurations across all categories for every range band
+    ///
+    /// Each band of the query is scored at its last meter, one short of the
+    /// exclusive `band_end` (or at its start for the open-ended band), so the
+    /// damage holds across the band. A width below 1 or a negative open band
+    /// start yields a single error.
+    pub fn range_band_winners(
+        &self,
+        query: &RangeBandQuery,
+    ) -> impl Stream<Item = Result<RangeBandWinner>> + '_ {
+        debug!("Starting streaming query for range band winners: {:?}", query);
+
+        let winners = checked_range_bands(query.band_width, query.open_band_start)
+            .map(|_| self.range_band_winners_after(query, None, None));
+        stream::once(async move { winners }).try_flatten()
+    }
+
+    /// Get a page of range band winners
+    ///
+    /// The query's limit stays the number of winners kept per band; `limit`
+    /// bounds the page size.
+    pub async fn range_band_winners_page(
+        &self,
+        query: &RangeBandQuery,
+        limit: i64,
+        cursor: Option<&Cursor>,
+    ) -> Result<Page<RangeBandWinner>> {
+        debug!(
+            "Querying page of range band winners (limit: {}, cursor: {:?})",
+            limit, cursor
+        );
+
+        checked_range_bands(query.band_width, query.open_band_start)?;
+        let after: Option<(i16, i64)> = cursor.map(Cursor::decode).transpose()?;
+        let items = self
+            .range_band_winners_after(query, after, Some(page_fetch_limit(limit)?))
+            .try_collect()
+            .await?;
+        Ok(Page::from_items(items, limit, |winner: &RangeBandWinner| {
+            (winner.band_start, winner.rank)
+        }))
+    }
+
+    /// Range band winners ordered by band and rank, starting after the `(band_start, rank)` key
+    fn range_band_winners_after(
+        &self,
+        query: &RangeBandQuery,
+        after: Option<(i16, i64)>,
+        page_limit: Option<i64>,
+    ) -> impl Stream<Item = Result<RangeBandWinner>> + '_ {
+        let (after_band, after_rank) = after.unzip();
+
+        sqlx::query_as!(
+            RangeBandWinner,
+            r#"
+            WITH bands AS (
+                SELECT
+                    band_start::INT2 as band_start,
+                    LEAST(band_start + $1::INT4, $2::INT4)::INT2 as band_end
+                FROM generate_series(0, $2::INT4 - 1, $1::INT4) as band_start
+                UNION ALL
+                SELECT $2::INT4::INT2, NULL::INT2
+            ),
+            effective_damage AS (
+                SELECT
+                    bands.band_start,
+                    bands.band_end,
+                    c.config_id,
+                    cd.range,
+                    cd.damage,
+                    was.pellet_count,
+                    cd.damage * GREATEST(COALESCE(was.pellet_count, 1), 1) as shot_damage,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY bands.band_start, c.config_id
+                        ORDER BY cd.range DESC
+                    ) as rn
+                FROM bands
+                CROSS JOIN configurations c
+                JOIN config_dropoffs cd ON c.config_id = cd.config_id
+                LEFT JOIN weapon_ammo_stats was
+                    ON c.weapon_id = was.weapon_id AND c.ammo_id = was.ammo_id
+                WHERE cd.range <= COALESCE(bands.band_end - 1, bands.band_start)
+            ),
+            scored AS (
+                SELECT
+                    ed.band_start,
+                    ed.band_end,
+                    ed.config_id,
+                    ed.range,
+                    ed.damage,
+                    ed.pellet_count,
+                    ed.shot_damage,
+                    CEIL($3::NUMERIC / ed.shot_damage)::INT4 as bullets_to_kill,
+                    ((CEIL($3::NUMERIC / ed.shot_damage) - 1) * 60000
+                        / COALESCE(NULLIF(c.rpm_auto, 0), NULLIF(c.rpm_single, 0)))::FLOAT8 as ttk_ms
+                FROM effective_damage ed
+                JOIN configurations c ON ed.config_id = c.config_id
+                WHERE ed.rn = 1
+            ),
+            ranked AS (
+                SELECT
+                    s.*,
+                    ROW_NUMBER() OVER (
+                        PARTITION BY s.band_start
+                        ORDER BY
+                            CASE WHEN $4::BOOL THEN s.ttk_ms END ASC NULLS LAST,
+                            s.shot_damage DESC,
+                            s.config_id
+                    ) as rank
+                FROM scored s
+            )
+            SELECT
+                r.band_start as "band_start!",
+                r.band_end,
+                r.rank as "rank!",
+                cat.category_name,
+                w.weapon_name,
+                b.barrel_name,
+                a.ammo_type_name,
+                r.range as "effective_range!",
+                r.damage as "damage!",
+                r.pellet_count,
+                r.bullets_to_kill,
+                r.ttk_ms,
+                c.velocity,
+                c.rpm_single,
+                c.rpm_burst,
+                c.rpm_auto
+            FROM ranked r
+            JOIN configurations c ON r.config_id = c.config_id
+            JOIN weapons w ON c.weapon_id = w.weapon_id
+            JOIN categories cat ON w.category_id = cat.category_id
+            JOIN barrels b ON c.barrel_id = b.barrel_id
+            JOIN ammo_types a ON c.ammo_id = a.ammo_id
+            WHERE r.rank <= $5
+              AND ($6::INT2 IS NULL OR (r.band_start, r.rank) > ($6, $7::INT8))
+            ORDER BY r.band_start, r.rank
+            LIMIT $8
+            "#,
+            i32::from(query.band_width),
+            i32::from(query.open_band_start),
+            query.target.effective_health(),
+            query.rank_by == RankBy::Ttk,
+            query.limit,
+            after_band,
+            after_rank,
+            page_limit
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get the configurations of a weapon that are not dominated on the given metrics
+    pub async fn pareto_configs(
+        &self,
+        weapon_name: &str,
+        metrics: &[ParetoMetric],
+        target: &TargetProfile,
+    ) -> Result<Vec<ParetoConfig>> {
+        debug!(
+            "Computing pareto frontier for weapon {} on {} metrics",
+            weapon_name,
+            metrics.len()
+        );
+
+        if metrics.is_empty() {
+            return Err(StatsError::QueryFailed(
+                "At least one pareto metric is required".to_string(),
+            ));
+        }
+
+        let configs: Vec<WeaponConfig> =
+            self.weapon_configs_grouped(weapon_name).try_collect().await?;
+
+        if configs.is_empty() {
+            return Err(StatsError::NotFound(format!("Weapon '{}'", weapon_name)));
+        }
+
+        Ok(pareto::frontier(configs, metrics, target))
+    }
+
+    /// Compare two configurations head to head at each of the given ranges
+    pub async fn compare(
+        &self,
+        config_a: i32,
+        config_b: i32,
+        ranges: &[i16],
+        target: &TargetProfile,
+    ) -> Result<Comparison> {
+        debug!(
+            "Comparing configurations {} and {} at {} ranges",
+            config_a,
+            config_b,
+            ranges.len()
+        );
+
+        let config_a = self.config(config_a).await?;
+        let config_b = self.config(config_b).await?;
+        Ok(comparison::compare(&config_a, &config_b, ranges, target))
+    }
+
+    /// A single configuration with its dropoffs and ammo stats
+    async fn config(&self, config_id: i32) -> Result<WeaponConfig> {
+        let mut config = std::pin::pin!(group_config_rows(self.config_dropoffs(config_id)))
+            .try_next()
+            .await?
+            .ok_or_else(|| StatsError::NotFound(format!("Configuration {}", config_id)))?;
+
+        let ammo_stats: Vec<WeaponAmmoStatsWithNames> =
+            self.weapon_ammo_stats(&config.weapon_name).try_collect().await?;
+        config.ammo_stats = ammo_stats
+            .into_iter()
+            .find(|stats| stats.ammo_type_name == config.ammo_type_name);
+        Ok(config)
+    }
+
+    /// Simulate accuracy-weighted TTK for every configuration of a weapon at a range
+    ///
+    /// All configurations share the profile's seed, so differences between
+    /// them come from their stats rather than from sampling noise.
+    pub async fn expected_ttk(
+        &self,
+        weapon_name: &str,
+        target_range: i16,
+        profile: &AccuracyProfile,
+        target: &TargetProfile,
+    ) -> Result<Vec<ExpectedTtk>> {
+        debug!(
+            "Simulating expected TTK at range {} for weapon: {} ({:?}, target: {})",
+            target_range, weapon_name, profile, target.profile_name
+        );
+
+        profile.validate().map_err(StatsError::QueryFailed)?;
+
+        let ammo_stats: Vec<WeaponAmmoStatsWithNames> =
+            self.weapon_ammo_stats(weapon_name).try_collect().await?;
+        let rows: Vec<DamageAtRange> = self
+            .damage_at_range(weapon_name, target_range)
+            .try_collect()
+            .await?;
+
+        if rows.is_empty() {
+            return Err(StatsError::NotFound(format!("Weapon '{}'", weapon_name)));
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let ammo = ammo_stats
+                    .iter()
+                    .find(|stats| stats.ammo_type_name == row.ammo_type_name);
+                let multipliers = ammo.map(|ammo| ammo.hitbox_multipliers());
+                let engagement = ammo.zip(multipliers).and_then(|(ammo, multipliers)| {
+                    Some(Engagement {
+                        damage: row.damage,
+                        pellet_count: ammo.pellet_count,
+                        multipliers,
+                        rpm: ballistics::fire_rate(row.rpm_single, row.rpm_auto)?,
+                        magazine_size: Some(ammo.magazine_size),
+                    })
+                });
+
+                ExpectedTtk {
+                    distribution: engagement
+                        .map(|engagement| simulation::simulate(&engagement, profile, target)),
+                    weapon_name: row.weapon_name,
+                    barrel_name: row.barrel_name,
+                    ammo_type_name: row.ammo_type_name,
+                    effective_range: row.effective_range,
+                    damage: row.damage,
+                    headshot_multiplier: multipliers
+                        .map(|multipliers| target.hit_multiplier(Hitbox::Head, &multipliers)),
+                }
+            })
+            .collect())
+    }
+
+    /// Get all stored target profiles
+    pub fn target_profiles(&self) -> impl Stream<Item = Result<TargetProfile>> + '_ {
+        debug!("Starting streaming query for target profiles");
+
+        sqlx::query_as!(
+            TargetProfile,
+            r#"
+            SELECT
+                profile_name,
+                base_health,
+                armor_health,
+                armor_damage_reduction,
+                limb_multiplier,
+                headshot_multiplier_override
+            FROM target_profiles
+            ORDER BY profile_id
+            "#
+        )
+        .fetch(self.db_manager.pool())
+        .map_err(|e| e.into())
+    }
+
+    /// Get a stored target profile by name
+    pub async fn target_profile(&self, profile_name: &str) -> Result<TargetProfile> {
+        debug!("Querying target profile: {}", profile_name);
+
+        sqlx::query_as!(
+            TargetProfile,
+            r#"
+            SELECT
+                profile_name,
+                base_health,
+                armor_health,
+                armor_damage_reduction,
+                limb_multiplier,
+                headshot_multiplier_override
+            FROM target_profiles
+            WHERE profile_name = $1
+            "#,
+            profile_name
+        )
+        .fetch_optional(self.db_manager.pool())
+        .await?
+        .ok_or_else(|| {
+            StatsError::NotFound(format!("Target profile '{}'", profile_name))
+        })
+    }
+
+    /// Get attachments compatible with a weapon, ordered by slot
+    pub fn weapon_attachments(
+        &self,
+        weapon_name: &str,
+    ) -> impl Stream<Item = Result<Attachment>> + '_ {
+        debug!(
+            "Starting streaming query for weapon attachments: {}",
+            weapon_name
+        );
+
+        sqlx::query_as!(
+            Attachment,
+            r#"
+            SELECT
+                a.attachment_id,
+                a.slot,
+                a.attachment_name,
+                a.velocity_modifier,
+                a.rpm_modifier,
+                a.spread_modifier
+            FROM attachments a
+            JOIN weapon_attachments wa ON a.attachment_id = wa.attachment_id
+            JOIN weapons w ON wa.weapon_id = w.weapon_id
+            WHERE w.weapon_name = $1
+            ORDER BY a.slot, a.attachment_name
+            "#,
+            weapon_name
         )
         .fetch(self.db_manager.pool())
         .map_err(|e| e.into())
     }
 
+    /// Get every valid full attachment loadout of a weapon with derived stats
+    ///
+    /// Each loadout has one attachment per slot the weapon supports; weapons
+    /// without attachments yield their bare configurations.
+    pub fn loadouts<'a>(
+        &'a self,
+        weapon_name: &'a str,
+    ) -> impl Stream<Item = Result<Loadout>> + 'a {
+        debug!("Starting streaming query for loadouts: {}", weapon_name);
+
+        stream::once(async move {
+            let configs = sqlx::query!(
+                r#"
+                SELECT
+                    c.config_id,
+                    w.weapon_name,
+                    b.barrel_name,
+                    a.ammo_type_name,
+                    c.velocity,
+                    c.rpm_single,
+                    c.rpm_burst,
+                    c.rpm_auto
+                FROM weapons w
+                JOIN configurations c ON w.weapon_id = c.weapon_id
+                JOIN barrels b ON c.barrel_id = b.barrel_id
+                JOIN ammo_types a ON c.ammo_id = a.ammo_id
+                WHERE w.weapon_name = $1
+                ORDER BY b.barrel_name, a.ammo_type_name
+                "#,
+                weapon_name
+            )
+            .fetch(self.db_manager.pool())
+            .map_ok(|row| Loadout {
+                config_id: row.config_id,
+                weapon_name: row.weapon_name,
+                barrel_name: row.barrel_name,
+                ammo_type_name: row.ammo_type_name,
+                attachments: Vec::new(),
+                velocity: row.velocity,
+                rpm_single: row.rpm_single,
+                rpm_burst: row.rpm_burst,
+                rpm_auto: row.rpm_auto,
+                spread_modifier: Decimal::ONE,
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+            let attachments: Vec<Attachment> =
+                self.weapon_attachments(weapon_name).try_collect().await?;
+
+            let loadouts = loadout::enumerate(configs, attachments)?;
+            Ok::<_, StatsError>(stream::iter(loadouts).map(Ok))
+        })
+        .try_flatten()
+    }
+
     /// Get complete weapon information including all configurations and stats with streaming
     /// This method returns the basic weapon info and streams for configurations and ammo stats
     pub async fn weapon_details(
@@ -313,26 +1744,51 @@ impl StatsClient {
         );
 
         // Get basic weapon info first
-        let weapon = sqlx::query_as!(
-            Weapon,
+        let weapon = self.weapon(weapon_name).await?;
+
+        // Return weapon info and streams for configurations and ammo stats
+        let config_stream = self.weapon_configs(weapon_name);
+        let ammo_stream = self.weapon_ammo_stats(weapon_name);
+
+        Ok((weapon, config_stream, ammo_stream))
+    }
+
+    /// Get complete weapon information as a single serializable aggregate
+    ///
+    /// Derived metrics are computed against the default target profile.
+    pub async fn weapon_details_aggregated(&self, weapon_name: &str) -> Result<WeaponDetails> {
+        debug!("Querying aggregated weapon details: {}", weapon_name);
+
+        let row = sqlx::query!(
             r#"
-            SELECT w.weapon_id, w.weapon_name, w.category_id
+            SELECT w.weapon_id, w.weapon_name, w.category_id, c.category_name
             FROM weapons w
+            JOIN categories c ON w.category_id = c.category_id
             WHERE w.weapon_name = $1
             "#,
             weapon_name
         )
         .fetch_optional(self.db_manager.pool())
         .await?
-        .ok_or_else(|| {
-            crate::StatsError::QueryFailed(format!("Weapon '{}' not found", weapon_name))
-        })?;
+        .ok_or_else(|| StatsError::NotFound(format!("Weapon '{}'", weapon_name)))?;
 
-        // Return weapon info and streams for configurations and ammo stats
-        let config_stream = self.weapon_configs(weapon_name);
-        let ammo_stream = self.weapon_ammo_stats(weapon_name);
+        let configurations: Vec<WeaponConfig> =
+            self.weapon_configs_grouped(weapon_name).try_collect().await?;
+        let ammo_stats: Vec<WeaponAmmoStatsWithNames> =
+            self.weapon_ammo_stats(weapon_name).try_collect().await?;
+        let metrics = WeaponMetrics::from_configs(&configurations, &TargetProfile::default());
 
-        Ok((weapon, config_stream, ammo_stream))
+        Ok(WeaponDetails {
+            weapon: Weapon {
+                weapon_id: row.weapon_id,
+                weapon_name: row.weapon_name,
+                category_id: row.category_id,
+            },
+            category_name: row.category_name,
+            configurations,
+            ammo_stats,
+            metrics,
+        })
     }
 
     /// Get a reference to the database manager
@@ -0,0 +1,147 @@
+//! Arrow and Parquet export of the flat damage table
+//!
+//! Rows of `StatsClient::damage_table` become Arrow record batches with one
+//! column per field. Decimals are stored as `Decimal128` with the precision and
+//! scale of their database column, so no value is rounded through floats.
+
+use crate::models::DamageTableRow;
+use crate::{Result, StatsClient};
+use arrow::array::{ArrayRef, Decimal128Array, Int16Array, Int32Array, RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use futures::stream::TryChunksError;
+use futures::{future, Stream, TryStreamExt};
+use parquet::arrow::AsyncArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use tokio::io::AsyncWrite;
+use tracing::{debug, info};
+
+/// Rows per record batch written by `write_damage_table_parquet`
+pub const DEFAULT_BATCH_SIZE: usize = 8192;
+
+/// DECIMAL(5,1) damage
+const DAMAGE: DataType = DataType::Decimal128(5, 1);
+/// DECIMAL(4,2) reload times in seconds
+const RELOAD_TIME: DataType = DataType::Decimal128(4, 2);
+/// DECIMAL(3,1) headshot multiplier
+const HEADSHOT_MULTIPLIER: DataType = DataType::Decimal128(3, 1);
+
+/// Schema of the damage table record batches
+pub fn damage_table_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("config_id", DataType::Int32, false),
+        Field::new("weapon_name", DataType::Utf8, false),
+        Field::new("category_name", DataType::Utf8, false),
+        Field::new("barrel_name", DataType::Utf8, false),
+        Field::new("ammo_type_name", DataType::Utf8, false),
+        Field::new("range", DataType::Int16, false),
+        Field::new("damage", DAMAGE, false),
+        Field::new("velocity", DataType::Int16, false),
+        Field::new("rpm_single", DataType::Int16, true),
+        Field::new("rpm_burst", DataType::Int16, true),
+        Field::new("rpm_auto", DataType::Int16, true),
+        Field::new("magazine_size", DataType::Int16, false),
+        Field::new("empty_reload_time", RELOAD_TIME, true),
+        Field::new("tactical_reload_time", RELOAD_TIME, true),
+        Field::new("headshot_multiplier", HEADSHOT_MULTIPLIER, false),
+        Field::new("pellet_count", DataType::Int16, true),
+    ]))
+}
+
+/// Decimal column with the precision and scale of `data_type`
+fn decimals(
+    values: impl Iterator<Item = Option<Decimal>>,
+    data_type: &DataType,
+) -> Result<ArrayRef> {
+    let DataType::Decimal128(precision, scale) = *data_type else {
+        unreachable!("decimal columns use Decimal128");
+    };
+    let array = values
+        .map(|value| {
+            value.map(|mut value| {
+                value.rescale(scale as u32);
+                value.mantissa()
+            })
+        })
+        .collect::<Decimal128Array>()
+        .with_precision_and_scale(precision, scale)?;
+    Ok(Arc::new(array))
+}
+
+/// Convert damage table rows into one record batch
+pub fn damage_table_batch(rows: &[DamageTableRow]) -> Result<RecordBatch> {
+    let strings = |field: fn(&DamageTableRow) -> &str| -> ArrayRef {
+        Arc::new(StringArray::from_iter_values(rows.iter().map(field)))
+    };
+    let smallints = |field: fn(&DamageTableRow) -> Option<i16>| -> ArrayRef {
+        Arc::new(rows.iter().map(field).collect::<Int16Array>())
+    };
+
+    let columns = vec![
+        Arc::new(Int32Array::from_iter_values(
+            rows.iter().map(|row| row.config_id),
+        )) as ArrayRef,
+        strings(|row| &row.weapon_name),
+        strings(|row| &row.category_name),
+        strings(|row| &row.barrel_name),
+        strings(|row| &row.ammo_type_name),
+        smallints(|row| Some(row.range)),
+        decimals(rows.iter().map(|row| Some(row.damage)), &DAMAGE)?,
+        smallints(|row| Some(row.velocity)),
+        smallints(|row| row.rpm_single),
+        smallints(|row| row.rpm_burst),
+        smallints(|row| row.rpm_auto),
+        smallints(|row| Some(row.magazine_size)),
+        decimals(rows.iter().map(|row| row.empty_reload_time), &RELOAD_TIME)?,
+        decimals(
+            rows.iter().map(|row| row.tactical_reload_time),
+            &RELOAD_TIME,
+        )?,
+        decimals(
+            rows.iter().map(|row| Some(row.headshot_multiplier)),
+            &HEADSHOT_MULTIPLIER,
+        )?,
+        smallints(|row| row.pellet_count),
+    ];
+    Ok(RecordBatch::try_new(damage_table_schema(), columns)?)
+}
+
+/// Stream the damage table as record batches of up to `batch_size` rows
+pub fn damage_table_batches(
+    client: &StatsClient,
+    batch_size: usize,
+) -> impl Stream<Item = Result<RecordBatch>> + '_ {
+    client
+        .damage_table()
+        .try_chunks(batch_size)
+        .map_err(|TryChunksError(_, err)| err)
+        .and_then(|rows| future::ready(damage_table_batch(&rows)))
+}
+
+/// Write the damage table as a Snappy-compressed Parquet file
+///
+/// Encoded row groups are flushed to `writer` asynchronously, so a slow file or
+/// socket never blocks the runtime.
+pub async fn write_damage_table_parquet<W: AsyncWrite + Unpin + Send>(
+    client: &StatsClient,
+    writer: W,
+) -> Result<()> {
+    info!("Writing damage table as Parquet");
+
+    let properties = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut writer = AsyncArrowWriter::try_new(writer, damage_table_schema(), Some(properties))?;
+
+    let mut batches = std::pin::pin!(damage_table_batches(client, DEFAULT_BATCH_SIZE));
+    while let Some(batch) = batches.try_next().await? {
+        debug!("Writing batch of {} rows", batch.num_rows());
+        writer.write(&batch).await?;
+    }
+    let metadata = writer.close().await?;
+
+    info!("Wrote {} damage table rows", metadata.num_rows);
+    Ok(())
+}
@@ -0,0 +1,214 @@
+//! Head-to-head comparison of two weapon configurations
+
+use crate::ballistics;
+use crate::models::{Damage, TargetProfile, WeaponConfig};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Side of a comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Side {
+    A,
+    B,
+}
+
+/// Identity and handling of a compared configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparedConfig {
+    pub config_id: i32,
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    pub velocity: i16,
+    pub fire_rate: Option<i16>,
+}
+
+/// Both configurations evaluated at one range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeComparison {
+    pub range: i16,
+    pub damage_a: Option<Damage>,
+    pub damage_b: Option<Damage>,
+    pub bullets_to_kill_a: Option<u32>,
+    pub bullets_to_kill_b: Option<u32>,
+    pub ttk_ms_a: Option<f64>,
+    pub ttk_ms_b: Option<f64>,
+    /// Milliseconds by which A kills faster than B (negative when B is faster)
+    pub advantage_ms: Option<f64>,
+    /// Faster configuration, `None` on a tie or when either TTK is unknown
+    pub winner: Option<Side>,
+}
+
+/// Range at which the faster configuration changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Crossover {
+    /// Last compared range won by the previous winner
+    pub from_range: i16,
+    /// First compared range won by the new winner
+    pub to_range: i16,
+    pub winner: Side,
+}
+
+/// Structured head-to-head comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comparison {
+    pub config_a: ComparedConfig,
+    pub config_b: ComparedConfig,
+    pub ranges: Vec<RangeComparison>,
+    pub crossovers: Vec<Crossover>,
+}
+
+/// Identity of a configuration
+fn compared_config(config: &WeaponConfig) -> ComparedConfig {
+    ComparedConfig {
+        config_id: config.config_id,
+        weapon_name: config.weapon_name.clone(),
+        barrel_name: config.barrel_name.clone(),
+        ammo_type_name: config.ammo_type_name.clone(),
+        velocity: config.velocity,
+        fire_rate: config.fire_rate(),
+    }
+}
+
+/// Damage, bullets to kill and TTK of a configuration at a range
+fn evaluate(
+    config: &WeaponConfig,
+    range: i16,
+    health: Decimal,
+) -> (Option<Damage>, Option<u32>, Option<f64>) {
+    let btk = config.bullets_to_kill(range, health);
+    let ttk = btk
+        .zip(config.fire_rate())
+        .and_then(|(btk, rpm)| ballistics::time_to_kill_ms(btk, rpm));
+    (config.damage_at(range), btk, ttk)
+}
+
+/// Compare two configurations with their dropoffs and ammo stats at each range
+pub fn compare(
+    a: &WeaponConfig,
+    b: &WeaponConfig,
+    ranges: &[i16],
+    target: &TargetProfile,
+) -> Comparison {
+    let health = target.effective_health();
+
+    let mut ranges = ranges.to_vec();
+    ranges.sort_unstable();
+    ranges.dedup();
+
+    let ranges: Vec<RangeComparison> = ranges
+        .into_iter()
+        .map(|range| {
+            let (damage_a, bullets_to_kill_a, ttk_ms_a) = evaluate(a, range, health);
+            let (damage_b, bullets_to_kill_b, ttk_ms_b) = evaluate(b, range, health);
+            let advantage_ms = ttk_ms_a.zip(ttk_ms_b).map(|(a, b)| b - a);
+            let winner = match advantage_ms {
+                Some(advantage) if advantage > 0.0 => Some(Side::A),
+                Some(advantage) if advantage < 0.0 => Some(Side::B),
+                _ => None,
+            };
+
+            RangeComparison {
+                range,
+                damage_a,
+                damage_b,
+                bullets_to_kill_a,
+                bullets_to_kill_b,
+                ttk_ms_a,
+                ttk_ms_b,
+                advantage_ms,
+                winner,
+            }
+        })
+        .collect();
+
+    // Ties and unknown TTKs keep the previous winner
+    let mut crossovers = Vec::new();
+    let mut leader: Option<(i16, Side)> = None;
+    for range in &ranges {
+        let Some(winner) = range.winner else {
+            continue;
+        };
+        match leader {
+            Some((from_range, side)) if side != winner => crossovers.push(Crossover {
+                from_range,
+                to_range: range.range,
+                winner,
+            }),
+            _ => {}
+        }
+        leader = Some((range.range, winner));
+    }
+
+    Comparison {
+        config_a: compared_config(a),
+        config_b: compared_config(b),
+        ranges,
+        crossovers,
+    }
+}
+
+/// Damage of two ammo types at one dropoff range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmmoDropoffDelta {
+    pub range: i16,
+    pub damage_a: Option<Damage>,
+    pub damage_b: Option<Damage>,
+    /// Damage gained by switching from ammo A to ammo B
+    pub damage_change: Option<Damage>,
+}
+
+/// Effect of switching a weapon and barrel from ammo A to ammo B
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AmmoComparison {
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_a: String,
+    pub ammo_b: String,
+    pub velocity_a: i16,
+    pub velocity_b: i16,
+    /// Velocity change from A to B in percent of A
+    pub velocity_change_pct: Option<f64>,
+    /// Damage at every dropoff range of either ammo type
+    pub dropoffs: Vec<AmmoDropoffDelta>,
+}
+
+/// Compare two configurations that differ only in ammo type
+pub fn compare_ammo(a: &WeaponConfig, b: &WeaponConfig) -> AmmoComparison {
+    let mut ranges: Vec<i16> = a
+        .dropoffs
+        .iter()
+        .chain(&b.dropoffs)
+        .map(|dropoff| dropoff.range)
+        .collect();
+    ranges.sort_unstable();
+    ranges.dedup();
+
+    let dropoffs = ranges
+        .into_iter()
+        .map(|range| {
+            let damage_a = a.damage_at(range);
+            let damage_b = b.damage_at(range);
+            AmmoDropoffDelta {
+                range,
+                damage_a,
+                damage_b,
+                damage_change: damage_a.zip(damage_b).map(|(a, b)| b - a),
+            }
+        })
+        .collect();
+
+    AmmoComparison {
+        weapon_name: a.weapon_name.clone(),
+        barrel_name: a.barrel_name.clone(),
+        ammo_a: a.ammo_type_name.clone(),
+        ammo_b: b.ammo_type_name.clone(),
+        velocity_a: a.velocity,
+        velocity_b: b.velocity,
+        velocity_change_pct: (a.velocity != 0).then(|| {
+            f64::from(b.velocity - a.velocity) * 100.0 / f64::from(a.velocity)
+        }),
+        dropoffs,
+    }
+}
@@ -0,0 +1,305 @@
+//! CSV export and import in the layout described in SCHEMA.md
+//!
+//! Every table is one `<table>.csv` file whose header lists the columns in
+//! SCHEMA.md order. NULL is written as an empty field, decimals keep the scale
+//! of their column (e.g. `25.0` for damage).
+
+use super::DatabaseManager;
+use crate::{Result, StatsError};
+use futures::TryStreamExt;
+use sqlx::Row;
+use std::collections::HashSet;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// A table exchanged as one CSV file
+struct CsvTable {
+    name: &'static str,
+    columns: &'static [&'static str],
+    /// Columns of the primary key, used to order exported rows
+    key: &'static [&'static str],
+    /// SERIAL column whose sequence is advanced past imported ids
+    serial: Option<&'static str>,
+}
+
+impl CsvTable {
+    fn file_name(&self) -> String {
+        format!("{}.csv", self.name)
+    }
+}
+
+/// Tables in dependency order, referenced tables first
+const CSV_TABLES: [CsvTable; 10] = [
+    CsvTable {
+        name: "categories",
+        columns: &["category_id", "category_name"],
+        key: &["category_id"],
+        serial: Some("category_id"),
+    },
+    CsvTable {
+        name: "weapons",
+        columns: &["weapon_id", "weapon_name", "category_id"],
+        key: &["weapon_id"],
+        serial: Some("weapon_id"),
+    },
+    CsvTable {
+        name: "barrels",
+        columns: &["barrel_id", "barrel_name"],
+        key: &["barrel_id"],
+        serial: Some("barrel_id"),
+    },
+    CsvTable {
+        name: "ammo_types",
+        columns: &["ammo_id", "ammo_type_name"],
+        key: &["ammo_id"],
+        serial: Some("ammo_id"),
+    },
+    CsvTable {
+        name: "weapon_ammo_stats",
+        columns: &[
+            "weapon_id",
+            "ammo_id",
+            "magazine_size",
+            "empty_reload_time",
+            "tactical_reload_time",
+            "headshot_multiplier",
+            "pellet_count",
+            "upper_chest_multiplier",
+            "limb_multiplier",
+            "ammo_order",
+        ],
+        key: &["weapon_id", "ammo_id"],
+        serial: None,
+    },
+    CsvTable {
+        name: "configurations",
+        columns: &[
+            "config_id",
+            "weapon_id",
+            "barrel_id",
+            "ammo_id",
+            "velocity",
+            "rpm_single",
+            "rpm_burst",
+            "rpm_auto",
+            "recoil_vertical",
+            "recoil_horizontal",
+            "recoil_first_shot_multiplier",
+            "spread_ads",
+            "spread_hipfire",
+            "spread_bloom_per_shot",
+        ],
+        key: &["config_id"],
+        serial: Some("config_id"),
+    },
+    CsvTable {
+        name: "config_dropoffs",
+        columns: &["config_id", "range", "damage"],
+        key: &["config_id", "range"],
+        serial: None,
+    },
+    CsvTable {
+        name: "target_profiles",
+        columns: &[
+            "profile_id",
+            "profile_name",
+            "base_health",
+            "armor_health",
+            "armor_damage_reduction",
+            "limb_multiplier",
+            "headshot_multiplier_override",
+        ],
+        key: &["profile_id"],
+        serial: Some("profile_id"),
+    },
+    CsvTable {
+        name: "attachments",
+        columns: &[
+            "attachment_id",
+            "slot",
+            "attachment_name",
+            "velocity_modifier",
+            "rpm_modifier",
+            "spread_modifier",
+        ],
+        key: &["attachment_id"],
+        serial: Some("attachment_id"),
+    },
+    CsvTable {
+        name: "weapon_attachments",
+        columns: &["weapon_id", "attachment_id"],
+        key: &["weapon_id", "attachment_id"],
+        serial: None,
+    },
+];
+
+/// Check that a header names every column of a table exactly once
+fn validate_header(table: &CsvTable, header: &csv::StringRecord) -> Result<()> {
+    let file = table.file_name();
+    let mut seen = HashSet::new();
+    for column in header {
+        if !table.columns.contains(&column) {
+            return Err(StatsError::InvalidCsv(format!(
+                "{} has unknown column '{}'",
+                file, column
+            )));
+        }
+        if !seen.insert(column) {
+            return Err(StatsError::InvalidCsv(format!(
+                "{} has duplicate column '{}'",
+                file, column
+            )));
+        }
+    }
+    let missing: Vec<_> = table
+        .columns
+        .iter()
+        .filter(|column| !seen.contains(*column))
+        .copied()
+        .collect();
+    if !missing.is_empty() {
+        return Err(StatsError::InvalidCsv(format!(
+            "{} is missing columns {}",
+            file,
+            missing.join(", ")
+        )));
+    }
+    Ok(())
+}
+
+/// Parse the contents of a CSV file into one JSON object per row, empty fields as null
+fn read_rows(table: &CsvTable, data: &[u8]) -> Result<Vec<serde_json::Value>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(data);
+    let header = reader.headers()?.clone();
+    validate_header(table, &header)?;
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record?;
+            let row = header
+                .iter()
+                .zip(record.iter())
+                .map(|(column, value)| {
+                    let value = if value.is_empty() {
+                        serde_json::Value::Null
+                    } else {
+                        serde_json::Value::String(value.to_string())
+                    };
+                    (column.to_string(), value)
+                })
+                .collect();
+            Ok(serde_json::Value::Object(row))
+        })
+        .collect()
+}
+
+impl DatabaseManager {
+    /// Write every table to `<table>.csv` in `dir`, creating the directory if needed
+    pub async fn export_csv(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        info!("Exporting CSV files to {}", dir.display());
+        tokio::fs::create_dir_all(dir).await?;
+
+        for table in &CSV_TABLES {
+            let columns: Vec<_> = table
+                .columns
+                .iter()
+                .map(|column| format!("{}::text", column))
+                .collect();
+            let query = format!(
+                "SELECT {} FROM {} ORDER BY {}",
+                columns.join(", "),
+                table.name,
+                table.key.join(", ")
+            );
+
+            // Serialized in memory and written through `tokio::fs`, off the runtime threads
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            writer.write_record(table.columns)?;
+
+            let mut rows = sqlx::query(&query).fetch(self.pool());
+            let mut count = 0;
+            while let Some(row) = rows.try_next().await? {
+                let fields = (0..table.columns.len())
+                    .map(|idx| row.try_get::<Option<String>, _>(idx))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                writer.write_record(fields.iter().map(|field| field.as_deref().unwrap_or("")))?;
+                count += 1;
+            }
+            let data = writer.into_inner().map_err(|err| err.into_error())?;
+            tokio::fs::write(dir.join(table.file_name()), data).await?;
+
+            debug!("Exported {} rows to {}", count, table.file_name());
+        }
+
+        info!("CSV export completed successfully");
+        Ok(())
+    }
+
+    /// Replace all data with the contents of the CSV files in `dir`
+    ///
+    /// Every file from SCHEMA.md must be present and its header must name each
+    /// column of the table exactly once, in any order. The import runs in one
+    /// transaction, so on any error the existing data is left untouched.
+    pub async fn import_csv(&self, dir: impl AsRef<Path>) -> Result<()> {
+        let dir = dir.as_ref();
+        info!("Importing CSV files from {}", dir.display());
+
+        // Parse and validate every file before touching the database
+        let mut tables = Vec::with_capacity(CSV_TABLES.len());
+        for table in &CSV_TABLES {
+            let data = tokio::fs::read(dir.join(table.file_name())).await?;
+            tables.push((table, read_rows(table, &data)?));
+        }
+
+        let mut tx = self.pool().begin().await?;
+
+        for (table, _) in tables.iter().rev() {
+            sqlx::query(&format!("DELETE FROM {}", table.name))
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        for (table, rows) in tables {
+            let count = rows.len();
+            let columns = table.columns.join(", ");
+            let query = format!(
+                "INSERT INTO {table} ({columns}) \
+                 SELECT {columns} FROM json_populate_recordset(NULL::{table}, $1::json)",
+                table = table.name,
+                columns = columns
+            );
+            sqlx::query(&query)
+                .bind(serde_json::Value::Array(rows).to_string())
+                .execute(&mut *tx)
+                .await
+                .map_err(|err| match err {
+                    sqlx::Error::Database(err) => {
+                        StatsError::InvalidCsv(format!("{}: {}", table.file_name(), err))
+                    }
+                    err => err.into(),
+                })?;
+
+            if let Some(serial) = table.serial {
+                sqlx::query(&format!(
+                    "SELECT setval(pg_get_serial_sequence('{table}', '{serial}'), \
+                     COALESCE(MAX({serial}), 0) + 1, false) FROM {table}",
+                    table = table.name,
+                    serial = serial
+                ))
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            debug!("Imported {} rows from {}", count, table.file_name());
+        }
+
+        tx.commit().await?;
+
+        info!("CSV import completed successfully");
+        Ok(())
+    }
+}
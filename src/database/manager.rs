@@ -1,8 +1,12 @@
 //! Database manager for schema and data operations
 
-use crate::models::{DatabaseConfig, ValidationReport};
+use crate::models::{
+    AmmoStatData, AttachmentData, CategoryData, DamageDropoffData, DatabaseConfig, TargetProfile,
+    ValidationReport, WeaponData, WeaponStatData, WeaponsData,
+};
 use crate::{Result, StatsError};
-use sqlx::PgPool;
+use rust_decimal::Decimal;
+use sqlx::{FromRow, PgPool};
 use tracing::{debug, info};
 
 /// Manages database connections and operations
@@ -87,6 +91,9 @@ impl DatabaseManager {
                 tactical_reload_time DECIMAL(4,2),
                 headshot_multiplier DECIMAL(3,1) NOT NULL,
                 pellet_count SMALLINT DEFAULT 1,
+                upper_chest_multiplier DECIMAL(3,2),
+                limb_multiplier DECIMAL(3,2),
+                ammo_order SMALLINT NOT NULL DEFAULT 0,
                 PRIMARY KEY (weapon_id, ammo_id)
             )
             "#,
@@ -102,6 +109,12 @@ impl DatabaseManager {
                 rpm_single SMALLINT,
                 rpm_burst SMALLINT,
                 rpm_auto SMALLINT,
+                recoil_vertical DECIMAL(5,3),
+                recoil_horizontal DECIMAL(5,3),
+                recoil_first_shot_multiplier DECIMAL(3,2),
+                spread_ads DECIMAL(5,3),
+                spread_hipfire DECIMAL(5,3),
+                spread_bloom_per_shot DECIMAL(5,3),
                 UNIQUE(weapon_id, barrel_id, ammo_id)
             )
             "#,
@@ -115,6 +128,43 @@ impl DatabaseManager {
                 PRIMARY KEY (config_id, range)
             )
             "#,
+
+            // Target profiles for kill calculations
+            r#"
+            CREATE TABLE IF NOT EXISTS target_profiles (
+                profile_id SERIAL PRIMARY KEY,
+                profile_name VARCHAR(50) NOT NULL UNIQUE,
+                base_health SMALLINT NOT NULL,
+                armor_health SMALLINT NOT NULL DEFAULT 0,
+                armor_damage_reduction DECIMAL(3,2) NOT NULL DEFAULT 0,
+                limb_multiplier DECIMAL(3,2) NOT NULL DEFAULT 1,
+                headshot_multiplier_override DECIMAL(3,1),
+                CONSTRAINT target_profiles_armor_damage_reduction_check
+                    CHECK (armor_damage_reduction >= 0 AND armor_damage_reduction < 1)
+            )
+            "#,
+
+            // Attachments for optic, underbarrel and muzzle slots
+            r#"
+            CREATE TABLE IF NOT EXISTS attachments (
+                attachment_id SERIAL PRIMARY KEY,
+                slot VARCHAR(20) NOT NULL,
+                attachment_name VARCHAR(100) NOT NULL,
+                velocity_modifier DECIMAL(3,2) NOT NULL DEFAULT 1,
+                rpm_modifier DECIMAL(3,2) NOT NULL DEFAULT 1,
+                spread_modifier DECIMAL(3,2) NOT NULL DEFAULT 1,
+                UNIQUE(slot, attachment_name)
+            )
+            "#,
+
+            // Weapon attachment compatibility
+            r#"
+            CREATE TABLE IF NOT EXISTS weapon_attachments (
+                weapon_id INTEGER NOT NULL REFERENCES weapons(weapon_id),
+                attachment_id INTEGER NOT NULL REFERENCES attachments(attachment_id),
+                PRIMARY KEY (weapon_id, attachment_id)
+            )
+            "#,
         ];
 
         // Execute schema creation statements
@@ -122,6 +172,40 @@ impl DatabaseManager {
             sqlx::query(statement).execute(&self.pool).await?;
         }
 
+        // Columns added after their tables, missing from databases created earlier
+        let column_statements = [
+            "ALTER TABLE weapon_ammo_stats ADD COLUMN IF NOT EXISTS upper_chest_multiplier DECIMAL(3,2)",
+            "ALTER TABLE weapon_ammo_stats ADD COLUMN IF NOT EXISTS limb_multiplier DECIMAL(3,2)",
+            "ALTER TABLE weapon_ammo_stats ADD COLUMN IF NOT EXISTS ammo_order SMALLINT NOT NULL DEFAULT 0",
+            "ALTER TABLE configurations ADD COLUMN IF NOT EXISTS recoil_vertical DECIMAL(5,3)",
+            "ALTER TABLE configurations ADD COLUMN IF NOT EXISTS recoil_horizontal DECIMAL(5,3)",
+            "ALTER TABLE configurations ADD COLUMN IF NOT EXISTS recoil_first_shot_multiplier DECIMAL(3,2)",
+            "ALTER TABLE configurations ADD COLUMN IF NOT EXISTS spread_ads DECIMAL(5,3)",
+            "ALTER TABLE configurations ADD COLUMN IF NOT EXISTS spread_hipfire DECIMAL(5,3)",
+            "ALTER TABLE configurations ADD COLUMN IF NOT EXISTS spread_bloom_per_shot DECIMAL(5,3)",
+        ];
+
+        for statement in &column_statements {
+            sqlx::query(statement).execute(&self.pool).await?;
+        }
+
+        // Constraints added after their tables; ADD CONSTRAINT has no IF NOT EXISTS
+        let constraint_statements = [(
+            "target_profiles_armor_damage_reduction_check",
+            "ALTER TABLE target_profiles ADD CONSTRAINT target_profiles_armor_damage_reduction_check CHECK (armor_damage_reduction >= 0 AND armor_damage_reduction < 1)",
+        )];
+
+        for (name, statement) in constraint_statements {
+            let exists: bool =
+                sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = $1)")
+                    .bind(name)
+                    .fetch_one(&self.pool)
+                    .await?;
+            if !exists {
+                sqlx::query(statement).execute(&self.pool).await?;
+            }
+        }
+
         // Create indexes
         let index_statements = [
             "CREATE INDEX IF NOT EXISTS idx_weapons_category ON weapons(category_id)",
@@ -129,6 +213,7 @@ impl DatabaseManager {
             "CREATE INDEX IF NOT EXISTS idx_config_dropoffs_config ON config_dropoffs(config_id)",
             "CREATE INDEX IF NOT EXISTS idx_config_dropoffs_range ON config_dropoffs(range)",
             "CREATE INDEX IF NOT EXISTS idx_weapon_ammo_stats_weapon ON weapon_ammo_stats(weapon_id)",
+            "CREATE INDEX IF NOT EXISTS idx_weapon_attachments_weapon ON weapon_attachments(weapon_id)",
         ];
 
         for statement in &index_statements {
@@ -139,6 +224,22 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Bring an existing database up to the current schema
+    ///
+    /// Creates any tables and columns added since the database was first
+    /// initialized and seeds the built-in target profiles. Every step is idempotent, so this
+    /// runs on each startup.
+    pub async fn migrate(&self) -> Result<()> {
+        info!("Applying schema migrations");
+
+        self.create_schema().await?;
+
+        let mut conn = self.pool.acquire().await?;
+        insert_preset_profiles(&mut conn).await?;
+
+        Ok(())
+    }
+
     /// Populate database from embedded weapons data
     pub async fn populate_from_embedded_data(&self) -> Result<()> {
         info!("Populating database from embedded weapons data");
@@ -149,13 +250,12 @@ impl DatabaseManager {
         self.populate_from_json_str(WEAPONS_JSON).await
     }
 
-    /// Internal method to populate from JSON string
-    async fn populate_from_json_str(&self, json_content: &str) -> Result<()> {
-        use crate::models::WeaponsData;
+    /// Populate database from a weapons.json document
+    pub async fn populate_from_json_str(&self, json_content: &str) -> Result<()> {
         use std::collections::{HashMap, HashSet};
 
         let weapons_data: WeaponsData =
-            serde_json::from_str(json_content).map_err(|e| StatsError::ParseError(e))?;
+            serde_json::from_str(json_content).map_err(StatsError::ParseError)?;
 
         debug!(
             "Parsed {} categories from JSON",
@@ -183,17 +283,20 @@ impl DatabaseManager {
                     ammo_types.insert(stat.ammo_type.clone());
                 }
 
-                // Process ammo stats
-                for (ammo_name, ammo_stat) in &weapon.ammo_stats {
+                // Process ammo stats, remembering their order within the weapon
+                for (ammo_order, (ammo_name, ammo_stat)) in weapon.ammo_stats.iter().enumerate() {
                     ammo_types.insert(ammo_name.clone());
                     weapon_ammo_stats.push((
                         weapon_id,
+                        ammo_order as i16,
                         ammo_name.clone(),
                         ammo_stat.mag_size,
                         ammo_stat.empty_reload,
                         ammo_stat.tactical_reload,
                         ammo_stat.headshot_multiplier,
                         ammo_stat.pellet_count,
+                        ammo_stat.upper_chest_multiplier,
+                        ammo_stat.limb_multiplier,
                     ));
                 }
             }
@@ -241,6 +344,11 @@ impl DatabaseManager {
             .await?;
         }
 
+        let weapon_id_map: HashMap<String, i32> = weapons
+            .iter()
+            .map(|(weapon_id, weapon_name, _)| (weapon_name.clone(), *weapon_id))
+            .collect();
+
         // Insert weapons
         for (weapon_id, weapon_name, category_id) in weapons {
             sqlx::query(
@@ -269,17 +377,20 @@ impl DatabaseManager {
         // Insert weapon ammo stats
         for (
             weapon_id,
+            ammo_order,
             ammo_name,
             mag_size,
             empty_reload,
             tactical_reload,
             headshot_mult,
             pellet_count,
+            upper_chest_mult,
+            limb_mult,
         ) in weapon_ammo_stats
         {
             if let Some(&ammo_id) = ammo_id_map.get(&ammo_name) {
                 sqlx::query(
-                    "INSERT INTO weapon_ammo_stats (weapon_id, ammo_id, magazine_size, empty_reload_time, tactical_reload_time, headshot_multiplier, pellet_count) VALUES ($1, $2, $3, $4, $5, $6, $7) ON CONFLICT (weapon_id, ammo_id) DO NOTHING"
+                    "INSERT INTO weapon_ammo_stats (weapon_id, ammo_id, magazine_size, empty_reload_time, tactical_reload_time, headshot_multiplier, pellet_count, upper_chest_multiplier, limb_multiplier, ammo_order) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) ON CONFLICT (weapon_id, ammo_id) DO NOTHING"
                 )
                 .bind(weapon_id)
                 .bind(ammo_id)
@@ -288,6 +399,9 @@ impl DatabaseManager {
                 .bind(tactical_reload)
                 .bind(headshot_mult)
                 .bind(pellet_count)
+                .bind(upper_chest_mult)
+                .bind(limb_mult)
+                .bind(ammo_order)
                 .execute(&mut *tx)
                 .await?;
             }
@@ -317,7 +431,7 @@ impl DatabaseManager {
                     ) {
                         // Insert configuration
                         sqlx::query(
-                            "INSERT INTO configurations (config_id, weapon_id, barrel_id, ammo_id, velocity, rpm_single, rpm_burst, rpm_auto) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (weapon_id, barrel_id, ammo_id) DO NOTHING"
+                            "INSERT INTO configurations (config_id, weapon_id, barrel_id, ammo_id, velocity, rpm_single, rpm_burst, rpm_auto, recoil_vertical, recoil_horizontal, recoil_first_shot_multiplier, spread_ads, spread_hipfire, spread_bloom_per_shot) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14) ON CONFLICT (weapon_id, barrel_id, ammo_id) DO NOTHING"
                         )
                         .bind(config_id)
                         .bind(weapon_id as i32)
@@ -327,6 +441,12 @@ impl DatabaseManager {
                         .bind(stat.rpm_single)
                         .bind(stat.rpm_burst)
                         .bind(stat.rpm_auto)
+                        .bind(stat.recoil_vertical)
+                        .bind(stat.recoil_horizontal)
+                        .bind(stat.recoil_first_shot_multiplier)
+                        .bind(stat.spread_ads)
+                        .bind(stat.spread_hipfire)
+                        .bind(stat.spread_bloom_per_shot)
                         .execute(&mut *tx)
                         .await?;
 
@@ -348,6 +468,37 @@ impl DatabaseManager {
             }
         }
 
+        // Insert attachments and their compatible weapons
+        for (idx, attachment) in weapons_data.attachments.iter().enumerate() {
+            let attachment_id = idx as i32 + 1;
+            sqlx::query(
+                "INSERT INTO attachments (attachment_id, slot, attachment_name, velocity_modifier, rpm_modifier, spread_modifier) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (slot, attachment_name) DO NOTHING"
+            )
+            .bind(attachment_id)
+            .bind(&attachment.slot)
+            .bind(&attachment.name)
+            .bind(attachment.velocity_modifier.unwrap_or(Decimal::ONE))
+            .bind(attachment.rpm_modifier.unwrap_or(Decimal::ONE))
+            .bind(attachment.spread_modifier.unwrap_or(Decimal::ONE))
+            .execute(&mut *tx)
+            .await?;
+
+            for weapon_name in &attachment.weapons {
+                if let Some(&weapon_id) = weapon_id_map.get(weapon_name) {
+                    sqlx::query(
+                        "INSERT INTO weapon_attachments (weapon_id, attachment_id) VALUES ($1, $2) ON CONFLICT (weapon_id, attachment_id) DO NOTHING"
+                    )
+                    .bind(weapon_id)
+                    .bind(attachment_id)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+            }
+        }
+
+        // Insert built-in target profiles
+        insert_preset_profiles(&mut tx).await?;
+
         // Commit transaction
         tx.commit().await?;
 
@@ -355,12 +506,212 @@ impl DatabaseManager {
         Ok(())
     }
 
+    /// Reconstruct the weapons.json structure from the normalized tables
+    ///
+    /// Categories, weapons and configurations keep their id order and ammo stats
+    /// their stored `ammo_order`, which is the order `populate_from_json_str`
+    /// inserted them in. Decimals serialize as numbers without trailing zeros
+    /// and absent optional fields are left out, as in weapons.json, so importing
+    /// a document and exporting it again yields the same JSON once its values
+    /// are rounded to their column scale (damage is DECIMAL(5,1)). Attachment
+    /// modifiers of 1 are left out, matching their default on import; target
+    /// profiles are not part of the format.
+    pub async fn export_weapons_data(&self) -> Result<WeaponsData> {
+        use indexmap::IndexMap;
+        use std::collections::HashMap;
+
+        info!("Exporting weapons data from database");
+
+        let categories: Vec<(i32, String)> =
+            sqlx::query_as("SELECT category_id, category_name FROM categories ORDER BY category_id")
+                .fetch_all(&self.pool)
+                .await?;
+        let mut weapons_data = WeaponsData {
+            categories: categories
+                .iter()
+                .map(|(_, name)| CategoryData {
+                    name: name.clone(),
+                    weapons: Vec::new(),
+                })
+                .collect(),
+            attachments: Vec::new(),
+        };
+        let category_idx: HashMap<i32, usize> = categories
+            .iter()
+            .enumerate()
+            .map(|(idx, (category_id, _))| (*category_id, idx))
+            .collect();
+
+        // Position of each weapon as (category index, weapon index)
+        let weapons: Vec<(i32, String, i32)> = sqlx::query_as(
+            "SELECT weapon_id, weapon_name, category_id FROM weapons ORDER BY weapon_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut weapon_position = HashMap::new();
+        let mut weapon_names = HashMap::new();
+        for (weapon_id, weapon_name, category_id) in weapons {
+            let category_idx = category_idx[&category_id];
+            let weapons = &mut weapons_data.categories[category_idx].weapons;
+            weapon_position.insert(weapon_id, (category_idx, weapons.len()));
+            weapon_names.insert(weapon_id, weapon_name.clone());
+            weapons.push(WeaponData {
+                name: weapon_name,
+                stats: Vec::new(),
+                ammo_stats: IndexMap::new(),
+            });
+        }
+
+        let mut dropoffs: HashMap<i32, Vec<DamageDropoffData>> = HashMap::new();
+        let rows: Vec<(i32, i16, Decimal)> = sqlx::query_as(
+            "SELECT config_id, range, damage FROM config_dropoffs ORDER BY config_id, range",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for (config_id, range, damage) in rows {
+            dropoffs.entry(config_id).or_default().push(DamageDropoffData {
+                damage: damage.normalize(),
+                range,
+            });
+        }
+
+        let configs: Vec<ConfigurationRow> = sqlx::query_as(
+            r#"
+            SELECT c.config_id, c.weapon_id, b.barrel_name, a.ammo_type_name, c.velocity,
+                   c.rpm_single, c.rpm_burst, c.rpm_auto, c.recoil_vertical, c.recoil_horizontal,
+                   c.recoil_first_shot_multiplier, c.spread_ads, c.spread_hipfire,
+                   c.spread_bloom_per_shot
+            FROM configurations c
+            JOIN barrels b ON b.barrel_id = c.barrel_id
+            JOIN ammo_types a ON a.ammo_id = c.ammo_id
+            ORDER BY c.config_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for config in configs {
+            let (category_idx, weapon_idx) = weapon_position[&config.weapon_id];
+            weapons_data.categories[category_idx].weapons[weapon_idx]
+                .stats
+                .push(WeaponStatData {
+                    barrel_type: config.barrel_name,
+                    dropoffs: dropoffs.remove(&config.config_id).unwrap_or_default(),
+                    velocity: config.velocity,
+                    rpm_single: config.rpm_single,
+                    rpm_burst: config.rpm_burst,
+                    rpm_auto: config.rpm_auto,
+                    ammo_type: config.ammo_type_name,
+                    recoil_vertical: config.recoil_vertical.map(|d| d.normalize()),
+                    recoil_horizontal: config.recoil_horizontal.map(|d| d.normalize()),
+                    recoil_first_shot_multiplier: config
+                        .recoil_first_shot_multiplier
+                        .map(|d| d.normalize()),
+                    spread_ads: config.spread_ads.map(|d| d.normalize()),
+                    spread_hipfire: config.spread_hipfire.map(|d| d.normalize()),
+                    spread_bloom_per_shot: config.spread_bloom_per_shot.map(|d| d.normalize()),
+                });
+        }
+
+        let ammo_stats: Vec<AmmoStatsRow> = sqlx::query_as(
+            r#"
+            SELECT was.weapon_id, a.ammo_type_name, was.magazine_size, was.empty_reload_time,
+                   was.tactical_reload_time, was.headshot_multiplier, was.pellet_count,
+                   was.upper_chest_multiplier, was.limb_multiplier
+            FROM weapon_ammo_stats was
+            JOIN ammo_types a ON a.ammo_id = was.ammo_id
+            ORDER BY was.weapon_id, was.ammo_order, a.ammo_type_name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for stats in ammo_stats {
+            let (category_idx, weapon_idx) = weapon_position[&stats.weapon_id];
+            weapons_data.categories[category_idx].weapons[weapon_idx]
+                .ammo_stats
+                .insert(
+                    stats.ammo_type_name,
+                    AmmoStatData {
+                        mag_size: stats.magazine_size,
+                        headshot_multiplier: stats.headshot_multiplier.normalize(),
+                        empty_reload: stats.empty_reload_time.map(|d| d.normalize()),
+                        tactical_reload: stats.tactical_reload_time.map(|d| d.normalize()),
+                        pellet_count: stats.pellet_count,
+                        upper_chest_multiplier: stats.upper_chest_multiplier.map(|d| d.normalize()),
+                        limb_multiplier: stats.limb_multiplier.map(|d| d.normalize()),
+                    },
+                );
+        }
+
+        let attachments: Vec<(i32, String, String, Decimal, Decimal, Decimal)> = sqlx::query_as(
+            r#"
+            SELECT attachment_id, attachment_name, slot, velocity_modifier, rpm_modifier,
+                   spread_modifier
+            FROM attachments
+            ORDER BY attachment_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let mut compatible: HashMap<i32, Vec<String>> = HashMap::new();
+        let rows: Vec<(i32, i32)> = sqlx::query_as(
+            "SELECT attachment_id, weapon_id FROM weapon_attachments \
+             ORDER BY attachment_id, weapon_id",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        for (attachment_id, weapon_id) in rows {
+            compatible
+                .entry(attachment_id)
+                .or_default()
+                .push(weapon_names[&weapon_id].clone());
+        }
+        let modifier = |value: Decimal| (value != Decimal::ONE).then(|| value.normalize());
+        weapons_data.attachments = attachments
+            .into_iter()
+            .map(|(attachment_id, name, slot, velocity, rpm, spread)| AttachmentData {
+                name,
+                slot,
+                weapons: compatible.remove(&attachment_id).unwrap_or_default(),
+                velocity_modifier: modifier(velocity),
+                rpm_modifier: modifier(rpm),
+                spread_modifier: modifier(spread),
+            })
+            .collect();
+
+        info!("Weapons data exported successfully");
+        Ok(weapons_data)
+    }
+
+    /// Insert or update a target profile by name
+    pub async fn save_target_profile(&self, profile: &TargetProfile) -> Result<()> {
+        info!("Saving target profile: {}", profile.profile_name);
+
+        profile.validate().map_err(StatsError::QueryFailed)?;
+
+        sqlx::query(
+            "INSERT INTO target_profiles (profile_name, base_health, armor_health, armor_damage_reduction, limb_multiplier, headshot_multiplier_override) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (profile_name) DO UPDATE SET base_health = EXCLUDED.base_health, armor_health = EXCLUDED.armor_health, armor_damage_reduction = EXCLUDED.armor_damage_reduction, limb_multiplier = EXCLUDED.limb_multiplier, headshot_multiplier_override = EXCLUDED.headshot_multiplier_override"
+        )
+        .bind(&profile.profile_name)
+        .bind(profile.base_health)
+        .bind(profile.armor_health)
+        .bind(profile.armor_damage_reduction)
+        .bind(profile.limb_multiplier)
+        .bind(profile.headshot_multiplier_override)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Reset database - Drop and recreate all tables
     pub async fn reset_database(&self) -> Result<()> {
         info!("Resetting database (drop and recreate schema)");
 
         // Drop all tables and sequences in correct order (reverse dependency order)
         let drop_statements = [
+            "DROP TABLE IF EXISTS weapon_attachments CASCADE",
+            "DROP TABLE IF EXISTS attachments CASCADE",
+            "DROP TABLE IF EXISTS target_profiles CASCADE",
             "DROP TABLE IF EXISTS config_dropoffs CASCADE",
             "DROP TABLE IF EXISTS configurations CASCADE", 
             "DROP TABLE IF EXISTS weapon_ammo_stats CASCADE",
@@ -374,6 +725,8 @@ impl DatabaseManager {
             "DROP SEQUENCE IF EXISTS barrels_barrel_id_seq CASCADE",
             "DROP SEQUENCE IF EXISTS ammo_types_ammo_id_seq CASCADE",
             "DROP SEQUENCE IF EXISTS configurations_config_id_seq CASCADE",
+            "DROP SEQUENCE IF EXISTS target_profiles_profile_id_seq CASCADE",
+            "DROP SEQUENCE IF EXISTS attachments_attachment_id_seq CASCADE",
         ];
 
         for statement in &drop_statements {
@@ -399,6 +752,18 @@ impl DatabaseManager {
         let mut tx = self.pool.begin().await?;
 
         // Delete data in correct order (reverse dependency order)
+        sqlx::query("DELETE FROM weapon_attachments")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM attachments")
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM target_profiles")
+            .execute(&mut *tx)
+            .await?;
+
         sqlx::query("DELETE FROM config_dropoffs")
             .execute(&mut *tx)
             .await?;
@@ -447,7 +812,7 @@ impl DatabaseManager {
         // Check table counts
         let tables = [
             "categories", "weapons", "barrels", "ammo_types", 
-            "weapon_ammo_stats", "configurations", "config_dropoffs"
+            "weapon_ammo_stats", "configurations", "config_dropoffs", "target_profiles"
         ];
 
         for table in &tables {
@@ -469,6 +834,7 @@ impl DatabaseManager {
             ("SELECT COUNT(*) FROM configurations c WHERE NOT EXISTS (SELECT 1 FROM weapons w WHERE w.weapon_id = c.weapon_id) OR NOT EXISTS (SELECT 1 FROM barrels b WHERE b.barrel_id = c.barrel_id) OR NOT EXISTS (SELECT 1 FROM ammo_types a WHERE a.ammo_id = c.ammo_id)", "configurations have invalid references"),
             ("SELECT COUNT(*) FROM config_dropoffs cd WHERE NOT EXISTS (SELECT 1 FROM configurations c WHERE c.config_id = cd.config_id)", "dropoffs reference non-existent configurations"),
             ("SELECT COUNT(*) FROM weapon_ammo_stats was WHERE NOT EXISTS (SELECT 1 FROM weapons w WHERE w.weapon_id = was.weapon_id) OR NOT EXISTS (SELECT 1 FROM ammo_types a WHERE a.ammo_id = was.ammo_id)", "ammo stats have invalid references"),
+            ("SELECT COUNT(*) FROM weapon_attachments wa WHERE NOT EXISTS (SELECT 1 FROM weapons w WHERE w.weapon_id = wa.weapon_id) OR NOT EXISTS (SELECT 1 FROM attachments a WHERE a.attachment_id = wa.attachment_id)", "weapon attachments have invalid references"),
         ];
 
         for (query, description) in integrity_checks {
@@ -488,3 +854,55 @@ impl DatabaseManager {
         Ok(report)
     }
 }
+
+/// Insert the built-in target profiles, keeping any existing profile of the same name
+async fn insert_preset_profiles(conn: &mut sqlx::PgConnection) -> Result<()> {
+    for profile in TargetProfile::presets() {
+        sqlx::query(
+            "INSERT INTO target_profiles (profile_name, base_health, armor_health, armor_damage_reduction, limb_multiplier, headshot_multiplier_override) VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (profile_name) DO NOTHING"
+        )
+        .bind(&profile.profile_name)
+        .bind(profile.base_health)
+        .bind(profile.armor_health)
+        .bind(profile.armor_damage_reduction)
+        .bind(profile.limb_multiplier)
+        .bind(profile.headshot_multiplier_override)
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Configuration row with barrel and ammo names, as exported to weapons.json
+#[derive(FromRow)]
+struct ConfigurationRow {
+    config_id: i32,
+    weapon_id: i32,
+    barrel_name: String,
+    ammo_type_name: String,
+    velocity: i16,
+    rpm_single: Option<i16>,
+    rpm_burst: Option<i16>,
+    rpm_auto: Option<i16>,
+    recoil_vertical: Option<Decimal>,
+    recoil_horizontal: Option<Decimal>,
+    recoil_first_shot_multiplier: Option<Decimal>,
+    spread_ads: Option<Decimal>,
+    spread_hipfire: Option<Decimal>,
+    spread_bloom_per_shot: Option<Decimal>,
+}
+
+/// Ammo stats row with the ammo type name, as exported to weapons.json
+#[derive(FromRow)]
+struct AmmoStatsRow {
+    weapon_id: i32,
+    ammo_type_name: String,
+    magazine_size: i16,
+    empty_reload_time: Option<Decimal>,
+    tactical_reload_time: Option<Decimal>,
+    headshot_multiplier: Decimal,
+    pellet_count: Option<i16>,
+    upper_chest_multiplier: Option<Decimal>,
+    limb_multiplier: Option<Decimal>,
+}
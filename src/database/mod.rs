@@ -1,5 +1,6 @@
 //! Database management modules
 
+pub mod export;
 pub mod manager;
 
 pub use manager::DatabaseManager;
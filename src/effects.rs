@@ -0,0 +1,234 @@
+//! Barrel and ammo effects relative to the baseline configuration
+//!
+//! Every non-Factory barrel is compared with the Factory barrel on the same
+//! ammo type, and every non-Standard ammo type with Standard ammo on the same
+//! barrel. Per-weapon deltas are then averaged per barrel or ammo name.
+
+use crate::models::{Damage, TargetProfile, WeaponConfig};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Barrel every other barrel is compared with
+pub const BASELINE_BARREL: &str = "Factory";
+
+/// Ammo type every other ammo type is compared with
+pub const BASELINE_AMMO: &str = "Standard";
+
+/// Part of a configuration that differs from the baseline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Modification {
+    Barrel,
+    Ammo,
+}
+
+/// Range band `[band_start, band_end)`, open-ended when `band_end` is `None`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeBand {
+    pub band_start: i16,
+    pub band_end: Option<i16>,
+}
+
+impl RangeBand {
+    /// Range at which the band is scored: its last meter, or its start when open-ended
+    pub fn scored_range(&self) -> i16 {
+        self.band_end
+            .map_or(self.band_start, |band_end| band_end - 1)
+    }
+}
+
+/// Bands `band_width` meters wide from 0, plus an open band from `open_band_start`
+///
+/// Matches the bands of `StatsClient::range_band_winners`.
+pub fn range_bands(band_width: i16, open_band_start: i16) -> Vec<RangeBand> {
+    let mut bands: Vec<RangeBand> = (0..open_band_start)
+        .step_by(band_width.max(1) as usize)
+        .map(|band_start| RangeBand {
+            band_start,
+            band_end: Some(band_start.saturating_add(band_width).min(open_band_start)),
+        })
+        .collect();
+    bands.push(RangeBand {
+        band_start: open_band_start,
+        band_end: None,
+    });
+    bands
+}
+
+/// Damage and bullets-to-kill change within one band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandDelta {
+    #[serde(flatten)]
+    pub band: RangeBand,
+    pub damage_change: Option<Damage>,
+    pub bullets_to_kill_change: Option<i64>,
+}
+
+/// Change of one configuration against its baseline configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDelta {
+    pub weapon_name: String,
+    pub modification: Modification,
+    /// Barrel or ammo name being evaluated
+    pub name: String,
+    pub config_id: i32,
+    pub baseline_config_id: i32,
+    pub velocity_change_pct: Option<f64>,
+    /// Change of the sustained fire rate, see `ballistics::fire_rate`
+    pub rpm_change: Option<i16>,
+    pub bands: Vec<BandDelta>,
+}
+
+/// Average change across the arsenal per band
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BandEffect {
+    #[serde(flatten)]
+    pub band: RangeBand,
+    pub mean_damage_change: Option<f64>,
+    pub mean_bullets_to_kill_change: Option<f64>,
+}
+
+/// Average effect of a barrel or ammo type across every weapon that has it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentEffect {
+    pub modification: Modification,
+    pub name: String,
+    /// Number of configuration pairs averaged
+    pub sample_count: usize,
+    pub weapon_count: usize,
+    pub mean_velocity_change_pct: Option<f64>,
+    pub mean_rpm_change: Option<f64>,
+    pub bands: Vec<BandEffect>,
+}
+
+/// Compare a configuration with its baseline
+fn delta(
+    modification: Modification,
+    config: &WeaponConfig,
+    baseline: &WeaponConfig,
+    bands: &[RangeBand],
+    health: Decimal,
+) -> ConfigDelta {
+    let btk = |config: &WeaponConfig, range| config.bullets_to_kill(range, health).map(i64::from);
+
+    ConfigDelta {
+        weapon_name: config.weapon_name.clone(),
+        modification,
+        name: match modification {
+            Modification::Barrel => config.barrel_name.clone(),
+            Modification::Ammo => config.ammo_type_name.clone(),
+        },
+        config_id: config.config_id,
+        baseline_config_id: baseline.config_id,
+        velocity_change_pct: (baseline.velocity != 0).then(|| {
+            f64::from(config.velocity - baseline.velocity) * 100.0 / f64::from(baseline.velocity)
+        }),
+        rpm_change: config
+            .fire_rate()
+            .zip(baseline.fire_rate())
+            .map(|(rpm, base)| rpm - base),
+        bands: bands
+            .iter()
+            .map(|band| {
+                let range = band.scored_range();
+                BandDelta {
+                    band: *band,
+                    damage_change: config
+                        .damage_at(range)
+                        .zip(baseline.damage_at(range))
+                        .map(|(damage, base)| damage - base),
+                    bullets_to_kill_change: btk(config, range)
+                        .zip(btk(baseline, range))
+                        .map(|(btk, base)| btk - base),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Deltas of every non-baseline barrel and ammo configuration of one weapon
+///
+/// Configurations without a matching baseline configuration are skipped.
+pub fn weapon_deltas(
+    configs: &[WeaponConfig],
+    bands: &[RangeBand],
+    target: &TargetProfile,
+) -> Vec<ConfigDelta> {
+    let health = target.effective_health();
+    let find = |barrel: &str, ammo: &str| {
+        configs
+            .iter()
+            .find(|config| config.barrel_name == barrel && config.ammo_type_name == ammo)
+    };
+
+    let mut deltas = Vec::new();
+    for config in configs {
+        if config.barrel_name != BASELINE_BARREL {
+            if let Some(baseline) = find(BASELINE_BARREL, &config.ammo_type_name) {
+                deltas.push(delta(Modification::Barrel, config, baseline, bands, health));
+            }
+        }
+        if config.ammo_type_name != BASELINE_AMMO {
+            if let Some(baseline) = find(&config.barrel_name, BASELINE_AMMO) {
+                deltas.push(delta(Modification::Ammo, config, baseline, bands, health));
+            }
+        }
+    }
+    deltas
+}
+
+/// Mean of the present values
+fn mean(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let (sum, count) = values.flatten().fold((0.0, 0usize), |(sum, count), value| {
+        (sum + value, count + 1)
+    });
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Average deltas per barrel or ammo name, ordered by modification and name
+pub fn aggregate(deltas: &[ConfigDelta], bands: &[RangeBand]) -> Vec<AttachmentEffect> {
+    let mut keys: Vec<(Modification, &str)> = deltas
+        .iter()
+        .map(|delta| (delta.modification, delta.name.as_str()))
+        .collect();
+    keys.sort_by_key(|(modification, name)| (*modification == Modification::Ammo, *name));
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|(modification, name)| {
+            let group: Vec<&ConfigDelta> = deltas
+                .iter()
+                .filter(|delta| delta.modification == modification && delta.name == name)
+                .collect();
+            let mut weapons: Vec<&str> = group.iter().map(|d| d.weapon_name.as_str()).collect();
+            weapons.dedup();
+
+            AttachmentEffect {
+                modification,
+                name: name.to_string(),
+                sample_count: group.len(),
+                weapon_count: weapons.len(),
+                mean_velocity_change_pct: mean(group.iter().map(|d| d.velocity_change_pct)),
+                mean_rpm_change: mean(group.iter().map(|d| d.rpm_change.map(f64::from))),
+                bands: bands
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, band)| BandEffect {
+                        band: *band,
+                        mean_damage_change: mean(group.iter().map(|d| {
+                            d.bands[idx]
+                                .damage_change
+                                .and_then(|change| change.to_f64())
+                        })),
+                        mean_bullets_to_kill_change: mean(group.iter().map(|d| {
+                            d.bands[idx]
+                                .bullets_to_kill_change
+                                .map(|change| change as f64)
+                        })),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
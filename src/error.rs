@@ -14,9 +14,32 @@ pub enum StatsError {
     #[error("Database query failed: {0}")]
     QueryFailed(String),
 
+    /// A named weapon, configuration or profile does not exist
+    #[error("{0} not found")]
+    NotFound(String),
+
     #[error("JSON parsing error: {0}")]
     ParseError(#[from] serde_json::Error),
 
+    #[error("CSV error: {0}")]
+    CsvError(#[from] csv::Error),
+
+    /// A CSV file does not match the layout in SCHEMA.md
+    #[error("Invalid CSV: {0}")]
+    InvalidCsv(String),
+
+    /// A rendered table was asked for a column its rows do not have
+    #[error("Unknown column '{column}', expected one of: {expected}")]
+    UnknownColumn { column: String, expected: String },
+
+    #[cfg(feature = "parquet")]
+    #[error("Arrow error: {0}")]
+    ArrowError(#[from] arrow::error::ArrowError),
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    ParquetError(#[from] parquet::errors::ParquetError),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
@@ -0,0 +1,839 @@
+//! GraphQL schema over the weapon statistics tables
+//!
+//! Every table created by `DatabaseManager::create_schema` except the target
+//! profiles and attachments is exposed as an object type, so clients can select
+//! nested data such as categories → weapons → configurations → dropoffs in one
+//! request. Relations resolve through a shared `DataLoader`, which batches the
+//! lookups of one nesting level into a single `= ANY($1)` query instead of one
+//! query per parent.
+
+use crate::ballistics;
+use crate::models::{
+    AmmoType, Angle, Barrel, BestConfigInCategory, Category, ConfigDropoff, ConfigQuery,
+    ConfigSort, Configuration, Damage, HeadshotMultiplier, Modifier, ReloadTime, SortOrder, Weapon,
+    WeaponAmmoStats,
+};
+use crate::{StatsClient, StatsError};
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{Context, EmptyMutation, EmptySubscription, InputObject, Object, Schema};
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+/// Deepest selection accepted by the schema
+pub const MAX_QUERY_DEPTH: usize = 12;
+
+/// Number of configurations returned by `ranking` without `limit`
+pub const DEFAULT_RANKING_LIMIT: i64 = 10;
+
+/// Executable schema
+pub type StatsSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the schema over a shared client
+pub fn schema(client: Arc<StatsClient>) -> StatsSchema {
+    let loader = DataLoader::new(
+        StatsLoader {
+            client: client.clone(),
+        },
+        tokio::spawn,
+    );
+
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(client)
+        .data(loader)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .finish()
+}
+
+/// Routes serving the schema: `POST /graphql` executes a query, `GET /graphql`
+/// returns the schema in SDL
+pub fn router<S: Clone + Send + Sync + 'static>(client: Arc<StatsClient>) -> Router<S> {
+    Router::new()
+        .route("/graphql", post(execute).get(sdl))
+        .with_state(schema(client))
+}
+
+async fn execute(
+    State(schema): State<StatsSchema>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request).await)
+}
+
+async fn sdl(State(schema): State<StatsSchema>) -> String {
+    schema.sdl()
+}
+
+fn client<'a>(ctx: &Context<'a>) -> &'a StatsClient {
+    ctx.data_unchecked::<Arc<StatsClient>>()
+}
+
+fn loader<'a>(ctx: &Context<'a>) -> &'a DataLoader<StatsLoader> {
+    ctx.data_unchecked::<DataLoader<StatsLoader>>()
+}
+
+/// Load a related row that must exist because of a foreign key
+async fn load_related<K>(ctx: &Context<'_>, key: K) -> async_graphql::Result<K::Row>
+where
+    K: Related + Send + Sync + Hash + Eq + Clone + 'static,
+    StatsLoader: Loader<K, Value = K::Row, Error = Arc<StatsError>>,
+{
+    loader(ctx)
+        .load_one(key.clone())
+        .await?
+        .ok_or_else(|| key.missing().into())
+}
+
+/// Load the rows related to a parent, empty when it has none
+async fn load_many<K>(ctx: &Context<'_>, key: K) -> async_graphql::Result<Vec<K::Row>>
+where
+    K: Related + Send + Sync + Hash + Eq + Clone + 'static,
+    StatsLoader: Loader<K, Value = Vec<K::Row>, Error = Arc<StatsError>>,
+{
+    Ok(loader(ctx).load_one(key).await?.unwrap_or_default())
+}
+
+/// Loader key with the row type it relates to
+trait Related {
+    type Row: Send + Sync + Clone + 'static;
+
+    /// Error of a missing row
+    fn missing(&self) -> StatsError;
+}
+
+macro_rules! loader_key {
+    ($(#[$meta:meta])* $name:ident, $row:ty, $what:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        struct $name(i32);
+
+        impl Related for $name {
+            type Row = $row;
+
+            fn missing(&self) -> StatsError {
+                StatsError::NotFound(format!(concat!($what, " {}"), self.0))
+            }
+        }
+    };
+}
+
+loader_key!(
+    /// Category by id
+    CategoryId, Category, "Category"
+);
+loader_key!(
+    /// Weapon by id
+    WeaponId, Weapon, "Weapon"
+);
+loader_key!(
+    /// Barrel by id
+    BarrelId, Barrel, "Barrel"
+);
+loader_key!(
+    /// Ammo type by id
+    AmmoId, AmmoType, "Ammo type"
+);
+loader_key!(
+    /// Configuration by id
+    ConfigId, Configuration, "Configuration"
+);
+loader_key!(
+    /// Weapons of a category
+    WeaponsInCategory, Weapon, "Category"
+);
+loader_key!(
+    /// Configurations of a weapon
+    ConfigsOfWeapon, Configuration, "Weapon"
+);
+loader_key!(
+    /// Ammo stats of a weapon
+    AmmoStatsOfWeapon, WeaponAmmoStats, "Weapon"
+);
+loader_key!(
+    /// Dropoffs of a configuration
+    DropoffsOfConfig, ConfigDropoff, "Configuration"
+);
+
+/// Batched relation lookups
+pub struct StatsLoader {
+    client: Arc<StatsClient>,
+}
+
+impl StatsLoader {
+    fn pool(&self) -> &sqlx::PgPool {
+        self.client.database_manager().pool()
+    }
+}
+
+fn ids<K: Copy>(keys: &[K], id: impl Fn(K) -> i32) -> Vec<i32> {
+    keys.iter().copied().map(id).collect()
+}
+
+fn shared(err: sqlx::Error) -> Arc<StatsError> {
+    Arc::new(err.into())
+}
+
+/// Group rows by key, keeping their query order within each key
+fn grouped<K: Hash + Eq, V>(rows: Vec<V>, key: impl Fn(&V) -> K) -> HashMap<K, Vec<V>> {
+    let mut groups: HashMap<K, Vec<V>> = HashMap::new();
+    for row in rows {
+        groups.entry(key(&row)).or_default().push(row);
+    }
+    groups
+}
+
+impl Loader<CategoryId> for StatsLoader {
+    type Value = Category;
+    type Error = Arc<StatsError>;
+
+    async fn load(
+        &self,
+        keys: &[CategoryId],
+    ) -> Result<HashMap<CategoryId, Category>, Self::Error> {
+        let rows = sqlx::query_as!(
+            Category,
+            r#"
+            SELECT category_id, category_name
+            FROM categories
+            WHERE category_id = ANY($1)
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (CategoryId(row.category_id), row))
+            .collect())
+    }
+}
+
+impl Loader<WeaponId> for StatsLoader {
+    type Value = Weapon;
+    type Error = Arc<StatsError>;
+
+    async fn load(&self, keys: &[WeaponId]) -> Result<HashMap<WeaponId, Weapon>, Self::Error> {
+        let rows = sqlx::query_as!(
+            Weapon,
+            r#"
+            SELECT weapon_id, weapon_name, category_id
+            FROM weapons
+            WHERE weapon_id = ANY($1)
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (WeaponId(row.weapon_id), row))
+            .collect())
+    }
+}
+
+impl Loader<BarrelId> for StatsLoader {
+    type Value = Barrel;
+    type Error = Arc<StatsError>;
+
+    async fn load(&self, keys: &[BarrelId]) -> Result<HashMap<BarrelId, Barrel>, Self::Error> {
+        let rows = sqlx::query_as!(
+            Barrel,
+            r#"
+            SELECT barrel_id, barrel_name
+            FROM barrels
+            WHERE barrel_id = ANY($1)
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (BarrelId(row.barrel_id), row))
+            .collect())
+    }
+}
+
+impl Loader<AmmoId> for StatsLoader {
+    type Value = AmmoType;
+    type Error = Arc<StatsError>;
+
+    async fn load(&self, keys: &[AmmoId]) -> Result<HashMap<AmmoId, AmmoType>, Self::Error> {
+        let rows = sqlx::query_as!(
+            AmmoType,
+            r#"
+            SELECT ammo_id, ammo_type_name
+            FROM ammo_types
+            WHERE ammo_id = ANY($1)
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (AmmoId(row.ammo_id), row))
+            .collect())
+    }
+}
+
+impl Loader<ConfigId> for StatsLoader {
+    type Value = Configuration;
+    type Error = Arc<StatsError>;
+
+    async fn load(
+        &self,
+        keys: &[ConfigId],
+    ) -> Result<HashMap<ConfigId, Configuration>, Self::Error> {
+        let rows = sqlx::query_as!(
+            Configuration,
+            r#"
+            SELECT
+                config_id, weapon_id, barrel_id, ammo_id, velocity,
+                rpm_single, rpm_burst, rpm_auto,
+                recoil_vertical, recoil_horizontal, recoil_first_shot_multiplier,
+                spread_ads, spread_hipfire, spread_bloom_per_shot
+            FROM configurations
+            WHERE config_id = ANY($1)
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (ConfigId(row.config_id), row))
+            .collect())
+    }
+}
+
+impl Loader<WeaponsInCategory> for StatsLoader {
+    type Value = Vec<Weapon>;
+    type Error = Arc<StatsError>;
+
+    async fn load(
+        &self,
+        keys: &[WeaponsInCategory],
+    ) -> Result<HashMap<WeaponsInCategory, Vec<Weapon>>, Self::Error> {
+        let rows = sqlx::query_as!(
+            Weapon,
+            r#"
+            SELECT weapon_id, weapon_name, category_id
+            FROM weapons
+            WHERE category_id = ANY($1)
+            ORDER BY weapon_name
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(grouped(rows, |row| WeaponsInCategory(row.category_id)))
+    }
+}
+
+impl Loader<ConfigsOfWeapon> for StatsLoader {
+    type Value = Vec<Configuration>;
+    type Error = Arc<StatsError>;
+
+    async fn load(
+        &self,
+        keys: &[ConfigsOfWeapon],
+    ) -> Result<HashMap<ConfigsOfWeapon, Vec<Configuration>>, Self::Error> {
+        let rows = sqlx::query_as!(
+            Configuration,
+            r#"
+            SELECT
+                c.config_id, c.weapon_id, c.barrel_id, c.ammo_id, c.velocity,
+                c.rpm_single, c.rpm_burst, c.rpm_auto,
+                c.recoil_vertical, c.recoil_horizontal, c.recoil_first_shot_multiplier,
+                c.spread_ads, c.spread_hipfire, c.spread_bloom_per_shot
+            FROM configurations c
+            JOIN barrels b ON c.barrel_id = b.barrel_id
+            JOIN ammo_types a ON c.ammo_id = a.ammo_id
+            WHERE c.weapon_id = ANY($1)
+            ORDER BY b.barrel_name, a.ammo_type_name
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(grouped(rows, |row| ConfigsOfWeapon(row.weapon_id)))
+    }
+}
+
+impl Loader<AmmoStatsOfWeapon> for StatsLoader {
+    type Value = Vec<WeaponAmmoStats>;
+    type Error = Arc<StatsError>;
+
+    async fn load(
+        &self,
+        keys: &[AmmoStatsOfWeapon],
+    ) -> Result<HashMap<AmmoStatsOfWeapon, Vec<WeaponAmmoStats>>, Self::Error> {
+        let rows = sqlx::query_as!(
+            WeaponAmmoStats,
+            r#"
+            SELECT
+                s.weapon_id, s.ammo_id, s.magazine_size,
+                s.empty_reload_time, s.tactical_reload_time, s.headshot_multiplier,
+                s.pellet_count, s.upper_chest_multiplier, s.limb_multiplier
+            FROM weapon_ammo_stats s
+            JOIN ammo_types a ON s.ammo_id = a.ammo_id
+            WHERE s.weapon_id = ANY($1)
+            ORDER BY a.ammo_type_name
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(grouped(rows, |row| AmmoStatsOfWeapon(row.weapon_id)))
+    }
+}
+
+impl Loader<DropoffsOfConfig> for StatsLoader {
+    type Value = Vec<ConfigDropoff>;
+    type Error = Arc<StatsError>;
+
+    async fn load(
+        &self,
+        keys: &[DropoffsOfConfig],
+    ) -> Result<HashMap<DropoffsOfConfig, Vec<ConfigDropoff>>, Self::Error> {
+        let rows = sqlx::query_as!(
+            ConfigDropoff,
+            r#"
+            SELECT config_id, range, damage
+            FROM config_dropoffs
+            WHERE config_id = ANY($1)
+            ORDER BY range
+            "#,
+            &ids(keys, |key| key.0)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(shared)?;
+
+        Ok(grouped(rows, |row| DropoffsOfConfig(row.config_id)))
+    }
+}
+
+/// Root query type
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// All categories ordered by name
+    async fn categories(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<CategoryNode>> {
+        Ok(client(ctx)
+            .categories()
+            .map_ok(|row| {
+                CategoryNode(Category {
+                    category_id: row.category_id,
+                    category_name: row.category_name,
+                })
+            })
+            .try_collect()
+            .await?)
+    }
+
+    /// Category by name
+    async fn category(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Option<CategoryNode>> {
+        found(client(ctx).category(&name).await.map(CategoryNode))
+    }
+
+    /// Weapons ordered by name, optionally only those of a category
+    async fn weapons(
+        &self,
+        ctx: &Context<'_>,
+        category: Option<String>,
+    ) -> async_graphql::Result<Vec<WeaponNode>> {
+        let client = client(ctx);
+        let mut weapons: Vec<Weapon> = match category {
+            Some(category) => client.weapons_by_category(&category).try_collect().await?,
+            None => {
+                client
+                    .all_weapons()
+                    .map_ok(|row| Weapon {
+                        weapon_id: row.weapon_id,
+                        weapon_name: row.weapon_name,
+                        category_id: row.category_id,
+                    })
+                    .try_collect()
+                    .await?
+            }
+        };
+        weapons.sort_by(|a, b| a.weapon_name.cmp(&b.weapon_name));
+        Ok(weapons.into_iter().map(WeaponNode).collect())
+    }
+
+    /// Weapon by name
+    async fn weapon(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+    ) -> async_graphql::Result<Option<WeaponNode>> {
+        found(client(ctx).weapon(&name).await.map(WeaponNode))
+    }
+
+    /// All barrels ordered by name
+    async fn barrels(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<BarrelNode>> {
+        Ok(client(ctx)
+            .barrels()
+            .map_ok(|row| {
+                BarrelNode(Barrel {
+                    barrel_id: row.barrel_id,
+                    barrel_name: row.barrel_name,
+                })
+            })
+            .try_collect()
+            .await?)
+    }
+
+    /// All ammo types ordered by name
+    async fn ammo_types(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<AmmoTypeNode>> {
+        Ok(client(ctx)
+            .ammo_types()
+            .map_ok(|row| {
+                AmmoTypeNode(AmmoType {
+                    ammo_id: row.ammo_id,
+                    ammo_type_name: row.ammo_type_name,
+                })
+            })
+            .try_collect()
+            .await?)
+    }
+
+    /// Configurations ranked by a stat, with damage evaluated at `range`
+    async fn ranking(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] filter: RankingFilter,
+        #[graphql(default = 0)] range: i16,
+        #[graphql(default)] sort_by: ConfigSort,
+        #[graphql(default)] order: SortOrder,
+        #[graphql(default_with = "DEFAULT_RANKING_LIMIT")] limit: i64,
+    ) -> async_graphql::Result<Vec<RankedConfiguration>> {
+        let query = ConfigQuery {
+            category: filter.category,
+            weapon: filter.weapon,
+            barrel: filter.barrel,
+            ammo: filter.ammo,
+            range,
+            sort: sort_by,
+            order,
+            limit: Some(limit),
+            ..ConfigQuery::default()
+        };
+        let rows: Vec<BestConfigInCategory> =
+            client(ctx).query_configs(&query).try_collect().await?;
+
+        Ok(rows
+            .into_iter()
+            .enumerate()
+            .map(|(idx, row)| RankedConfiguration {
+                rank: idx as i32 + 1,
+                row,
+            })
+            .collect())
+    }
+}
+
+/// Resolve a lookup by name to `null` when nothing has that name
+fn found<T>(result: crate::Result<T>) -> async_graphql::Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(StatsError::NotFound(_)) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Weapon category
+pub struct CategoryNode(Category);
+
+#[Object(name = "Category")]
+impl CategoryNode {
+    async fn id(&self) -> i32 {
+        self.0.category_id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.category_name
+    }
+
+    /// Weapons of the category ordered by name
+    async fn weapons(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<WeaponNode>> {
+        let weapons = load_many(ctx, WeaponsInCategory(self.0.category_id)).await?;
+        Ok(weapons.into_iter().map(WeaponNode).collect())
+    }
+}
+
+/// Weapon
+pub struct WeaponNode(Weapon);
+
+#[Object(name = "Weapon")]
+impl WeaponNode {
+    async fn id(&self) -> i32 {
+        self.0.weapon_id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.weapon_name
+    }
+
+    async fn category(&self, ctx: &Context<'_>) -> async_graphql::Result<CategoryNode> {
+        Ok(CategoryNode(
+            load_related(ctx, CategoryId(self.0.category_id)).await?,
+        ))
+    }
+
+    /// Configurations ordered by barrel and ammo name
+    async fn configurations(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ConfigurationNode>> {
+        let configs = load_many(ctx, ConfigsOfWeapon(self.0.weapon_id)).await?;
+        Ok(configs.into_iter().map(ConfigurationNode).collect())
+    }
+
+    /// Stats per ammo type ordered by ammo name
+    async fn ammo_stats(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<AmmoStatsNode>> {
+        let stats = load_many(ctx, AmmoStatsOfWeapon(self.0.weapon_id)).await?;
+        Ok(stats.into_iter().map(AmmoStatsNode).collect())
+    }
+}
+
+/// Barrel
+pub struct BarrelNode(Barrel);
+
+#[Object(name = "Barrel")]
+impl BarrelNode {
+    async fn id(&self) -> i32 {
+        self.0.barrel_id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.barrel_name
+    }
+}
+
+/// Ammo type
+pub struct AmmoTypeNode(AmmoType);
+
+#[Object(name = "AmmoType")]
+impl AmmoTypeNode {
+    async fn id(&self) -> i32 {
+        self.0.ammo_id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.ammo_type_name
+    }
+}
+
+/// Magazine, reload and multiplier stats of a weapon with one ammo type
+pub struct AmmoStatsNode(WeaponAmmoStats);
+
+#[Object(name = "AmmoStats")]
+impl AmmoStatsNode {
+    async fn ammo_type(&self, ctx: &Context<'_>) -> async_graphql::Result<AmmoTypeNode> {
+        Ok(AmmoTypeNode(
+            load_related(ctx, AmmoId(self.0.ammo_id)).await?,
+        ))
+    }
+
+    async fn magazine_size(&self) -> i16 {
+        self.0.magazine_size
+    }
+
+    async fn empty_reload_time(&self) -> Option<ReloadTime> {
+        self.0.empty_reload_time
+    }
+
+    async fn tactical_reload_time(&self) -> Option<ReloadTime> {
+        self.0.tactical_reload_time
+    }
+
+    async fn headshot_multiplier(&self) -> HeadshotMultiplier {
+        self.0.headshot_multiplier
+    }
+
+    async fn pellet_count(&self) -> Option<i16> {
+        self.0.pellet_count
+    }
+
+    async fn upper_chest_multiplier(&self) -> Option<Modifier> {
+        self.0.upper_chest_multiplier
+    }
+
+    async fn limb_multiplier(&self) -> Option<Modifier> {
+        self.0.limb_multiplier
+    }
+}
+
+/// Weapon configuration: one barrel and ammo type
+pub struct ConfigurationNode(Configuration);
+
+#[Object(name = "Configuration")]
+impl ConfigurationNode {
+    async fn id(&self) -> i32 {
+        self.0.config_id
+    }
+
+    async fn weapon(&self, ctx: &Context<'_>) -> async_graphql::Result<WeaponNode> {
+        Ok(WeaponNode(
+            load_related(ctx, WeaponId(self.0.weapon_id)).await?,
+        ))
+    }
+
+    async fn barrel(&self, ctx: &Context<'_>) -> async_graphql::Result<BarrelNode> {
+        Ok(BarrelNode(
+            load_related(ctx, BarrelId(self.0.barrel_id)).await?,
+        ))
+    }
+
+    async fn ammo_type(&self, ctx: &Context<'_>) -> async_graphql::Result<AmmoTypeNode> {
+        Ok(AmmoTypeNode(
+            load_related(ctx, AmmoId(self.0.ammo_id)).await?,
+        ))
+    }
+
+    async fn velocity(&self) -> i16 {
+        self.0.velocity
+    }
+
+    async fn rpm_single(&self) -> Option<i16> {
+        self.0.rpm_single
+    }
+
+    async fn rpm_burst(&self) -> Option<i16> {
+        self.0.rpm_burst
+    }
+
+    async fn rpm_auto(&self) -> Option<i16> {
+        self.0.rpm_auto
+    }
+
+    /// Sustained fire rate: automatic, else single fire; burst is not counted
+    async fn fire_rate(&self) -> Option<i16> {
+        ballistics::fire_rate(self.0.rpm_single, self.0.rpm_auto)
+    }
+
+    async fn recoil_vertical(&self) -> Option<Angle> {
+        self.0.recoil_vertical
+    }
+
+    async fn recoil_horizontal(&self) -> Option<Angle> {
+        self.0.recoil_horizontal
+    }
+
+    async fn recoil_first_shot_multiplier(&self) -> Option<Modifier> {
+        self.0.recoil_first_shot_multiplier
+    }
+
+    async fn spread_ads(&self) -> Option<Angle> {
+        self.0.spread_ads
+    }
+
+    async fn spread_hipfire(&self) -> Option<Angle> {
+        self.0.spread_hipfire
+    }
+
+    async fn spread_bloom_per_shot(&self) -> Option<Angle> {
+        self.0.spread_bloom_per_shot
+    }
+
+    /// Dropoffs ordered by range
+    async fn dropoffs(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<DropoffNode>> {
+        let dropoffs = load_many(ctx, DropoffsOfConfig(self.0.config_id)).await?;
+        Ok(dropoffs.into_iter().map(DropoffNode).collect())
+    }
+
+    /// Damage at `range` meters
+    async fn damage_at(
+        &self,
+        ctx: &Context<'_>,
+        range: i16,
+    ) -> async_graphql::Result<Option<Damage>> {
+        let dropoffs = load_many(ctx, DropoffsOfConfig(self.0.config_id)).await?;
+        Ok(ballistics::damage_at(
+            dropoffs
+                .iter()
+                .map(|dropoff| (dropoff.range, dropoff.damage)),
+            range,
+        ))
+    }
+}
+
+/// Damage from a range onwards
+pub struct DropoffNode(ConfigDropoff);
+
+#[Object(name = "Dropoff")]
+impl DropoffNode {
+    async fn range(&self) -> i16 {
+        self.0.range
+    }
+
+    async fn damage(&self) -> Damage {
+        self.0.damage
+    }
+}
+
+/// Names a ranking is restricted to; unset fields match everything
+#[derive(InputObject, Default)]
+pub struct RankingFilter {
+    pub category: Option<String>,
+    pub weapon: Option<String>,
+    pub barrel: Option<String>,
+    pub ammo: Option<String>,
+}
+
+/// Configuration at its position in a ranking
+pub struct RankedConfiguration {
+    rank: i32,
+    row: BestConfigInCategory,
+}
+
+#[Object]
+impl RankedConfiguration {
+    /// 1-based position
+    async fn rank(&self) -> i32 {
+        self.rank
+    }
+
+    /// Damage at the ranking range
+    async fn damage(&self) -> Damage {
+        self.row.damage
+    }
+
+    async fn magazine_size(&self) -> i16 {
+        self.row.magazine_size
+    }
+
+    async fn configuration(&self, ctx: &Context<'_>) -> async_graphql::Result<ConfigurationNode> {
+        Ok(ConfigurationNode(
+            load_related(ctx, ConfigId(self.row.config_id)).await?,
+        ))
+    }
+}
@@ -0,0 +1,1533 @@
+//! gRPC server and client for the stats API
+//!
+//! `proto/bf2042_stats.proto` mirrors the `StatsClient` methods listed in its
+//! service: the server-streaming RPCs forward the client's streams item by
+//! item, and `GrpcStatsClient` turns the responses back into the model types.
+//! Decimals travel as strings holding the exact database value.
+
+use crate::comparison::{ComparedConfig, Comparison, Crossover, RangeComparison, Side};
+use crate::models::{
+    AmmoTypeWithCount, BarrelWithCount, BestConfigInCategory, CategoryWithCount, ConfigQuery,
+    ConfigSort, DamageAtRange, DamageDropoff, RangeBandQuery, RangeBandWinner, RankBy, SortOrder,
+    TargetProfile, Weapon, WeaponAmmoStatsWithNames, WeaponConfig, WeaponConfigWithDropoffs,
+    WeaponDetails, WeaponMetrics, WeaponWithCategory,
+};
+use crate::pareto::{ParetoConfig, ParetoMetric};
+use crate::{Result, StatsClient, StatsError};
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use tonic::transport::Channel;
+use tonic::{Code, Request, Response, Status};
+
+/// Messages and service stubs generated from `proto/bf2042_stats.proto`
+pub mod proto {
+    tonic::include_proto!("bf2042_stats.v1");
+}
+
+use proto::stats_service_client::StatsServiceClient;
+use proto::stats_service_server::{StatsService, StatsServiceServer};
+
+/// Status of a failed request
+///
+/// The client reports unusable arguments, such as invalid range bands or
+/// request fields that do not convert to model types, as `QueryFailed`.
+fn status(err: StatsError) -> Status {
+    match err {
+        StatsError::NotFound(_) => Status::not_found(err.to_string()),
+        StatsError::QueryFailed(message) => Status::invalid_argument(message),
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+/// Error of a failed call, the inverse of `status`
+fn stats_error(status: Status) -> StatsError {
+    match status.code() {
+        Code::NotFound => StatsError::NotFound(
+            status
+                .message()
+                .strip_suffix(" not found")
+                .unwrap_or(status.message())
+                .to_string(),
+        ),
+        _ => StatsError::QueryFailed(format!("gRPC request failed: {}", status.message())),
+    }
+}
+
+fn range_arg(range: i32) -> std::result::Result<i16, Status> {
+    i16::try_from(range)
+        .map_err(|_| Status::invalid_argument(format!("Range {} is out of bounds", range)))
+}
+
+/// Target profile of a request, the unarmored soldier when absent
+fn target_arg(target: Option<proto::TargetProfile>) -> std::result::Result<TargetProfile, Status> {
+    let target = target.map(TargetProfile::try_from).transpose();
+    let target = target.map_err(status)?.unwrap_or_default();
+    target.validate().map_err(Status::invalid_argument)?;
+    Ok(target)
+}
+
+fn decimal(value: &str) -> Result<Decimal> {
+    Decimal::from_str(value)
+        .map_err(|_| StatsError::QueryFailed(format!("Invalid decimal '{}'", value)))
+}
+
+fn optional_decimal(value: Option<String>) -> Result<Option<Decimal>> {
+    value.as_deref().map(decimal).transpose()
+}
+
+fn small(value: i32) -> Result<i16> {
+    i16::try_from(value)
+        .map_err(|_| StatsError::QueryFailed(format!("Value {} out of range", value)))
+}
+
+fn optional_small(value: Option<i32>) -> Result<Option<i16>> {
+    value.map(small).transpose()
+}
+
+/// Known value of an enum field
+fn enumeration<E: TryFrom<i32>>(value: i32) -> Result<E> {
+    E::try_from(value).map_err(|_| StatsError::QueryFailed(format!("Unknown enum value {}", value)))
+}
+
+/// Message field that senders always set
+fn required<T>(value: Option<T>, field: &str) -> Result<T> {
+    value.ok_or_else(|| StatsError::QueryFailed(format!("Missing field '{}'", field)))
+}
+
+impl From<CategoryWithCount> for proto::Category {
+    fn from(row: CategoryWithCount) -> Self {
+        Self {
+            category_id: row.category_id,
+            category_name: row.category_name,
+            weapon_count: row.weapon_count,
+        }
+    }
+}
+
+impl TryFrom<proto::Category> for CategoryWithCount {
+    type Error = StatsError;
+
+    fn try_from(message: proto::Category) -> Result<Self> {
+        Ok(Self {
+            category_id: message.category_id,
+            category_name: message.category_name,
+            weapon_count: message.weapon_count,
+        })
+    }
+}
+
+impl From<Weapon> for proto::Weapon {
+    fn from(row: Weapon) -> Self {
+        Self {
+            weapon_id: row.weapon_id,
+            weapon_name: row.weapon_name,
+            category_id: row.category_id,
+        }
+    }
+}
+
+impl TryFrom<proto::Weapon> for Weapon {
+    type Error = StatsError;
+
+    fn try_from(message: proto::Weapon) -> Result<Self> {
+        Ok(Self {
+            weapon_id: message.weapon_id,
+            weapon_name: message.weapon_name,
+            category_id: message.category_id,
+        })
+    }
+}
+
+impl From<WeaponConfigWithDropoffs> for proto::WeaponConfigWithDropoffs {
+    fn from(row: WeaponConfigWithDropoffs) -> Self {
+        Self {
+            config_id: row.config_id,
+            weapon_name: row.weapon_name,
+            barrel_name: row.barrel_name,
+            ammo_type_name: row.ammo_type_name,
+            velocity: row.velocity.into(),
+            rpm_single: row.rpm_single.map(i32::from),
+            rpm_burst: row.rpm_burst.map(i32::from),
+            rpm_auto: row.rpm_auto.map(i32::from),
+            recoil_vertical: row.recoil_vertical.map(|value| value.to_string()),
+            recoil_horizontal: row.recoil_horizontal.map(|value| value.to_string()),
+            recoil_first_shot_multiplier: row
+                .recoil_first_shot_multiplier
+                .map(|value| value.to_string()),
+            spread_ads: row.spread_ads.map(|value| value.to_string()),
+            spread_hipfire: row.spread_hipfire.map(|value| value.to_string()),
+            spread_bloom_per_shot: row.spread_bloom_per_shot.map(|value| value.to_string()),
+            range: row.range.into(),
+            damage: row.damage.to_string(),
+        }
+    }
+}
+
+impl TryFrom<proto::WeaponConfigWithDropoffs> for WeaponConfigWithDropoffs {
+    type Error = StatsError;
+
+    fn try_from(message: proto::WeaponConfigWithDropoffs) -> Result<Self> {
+        Ok(Self {
+            config_id: message.config_id,
+            weapon_name: message.weapon_name,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            velocity: small(message.velocity)?,
+            rpm_single: optional_small(message.rpm_single)?,
+            rpm_burst: optional_small(message.rpm_burst)?,
+            rpm_auto: optional_small(message.rpm_auto)?,
+            recoil_vertical: optional_decimal(message.recoil_vertical)?,
+            recoil_horizontal: optional_decimal(message.recoil_horizontal)?,
+            recoil_first_shot_multiplier: optional_decimal(message.recoil_first_shot_multiplier)?,
+            spread_ads: optional_decimal(message.spread_ads)?,
+            spread_hipfire: optional_decimal(message.spread_hipfire)?,
+            spread_bloom_per_shot: optional_decimal(message.spread_bloom_per_shot)?,
+            range: small(message.range)?,
+            damage: decimal(&message.damage)?,
+        })
+    }
+}
+
+impl From<WeaponAmmoStatsWithNames> for proto::WeaponAmmoStatsWithNames {
+    fn from(row: WeaponAmmoStatsWithNames) -> Self {
+        Self {
+            weapon_name: row.weapon_name,
+            ammo_type_name: row.ammo_type_name,
+            magazine_size: row.magazine_size.into(),
+            empty_reload_time: row.empty_reload_time.map(|value| value.to_string()),
+            tactical_reload_time: row.tactical_reload_time.map(|value| value.to_string()),
+            headshot_multiplier: row.headshot_multiplier.to_string(),
+            pellet_count: row.pellet_count.map(i32::from),
+            upper_chest_multiplier: row.upper_chest_multiplier.map(|value| value.to_string()),
+            limb_multiplier: row.limb_multiplier.map(|value| value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<proto::WeaponAmmoStatsWithNames> for WeaponAmmoStatsWithNames {
+    type Error = StatsError;
+
+    fn try_from(message: proto::WeaponAmmoStatsWithNames) -> Result<Self> {
+        Ok(Self {
+            weapon_name: message.weapon_name,
+            ammo_type_name: message.ammo_type_name,
+            magazine_size: small(message.magazine_size)?,
+            empty_reload_time: optional_decimal(message.empty_reload_time)?,
+            tactical_reload_time: optional_decimal(message.tactical_reload_time)?,
+            headshot_multiplier: decimal(&message.headshot_multiplier)?,
+            pellet_count: optional_small(message.pellet_count)?,
+            upper_chest_multiplier: optional_decimal(message.upper_chest_multiplier)?,
+            limb_multiplier: optional_decimal(message.limb_multiplier)?,
+        })
+    }
+}
+
+impl From<DamageAtRange> for proto::ConfigDamageAtRange {
+    fn from(row: DamageAtRange) -> Self {
+        Self {
+            weapon_name: row.weapon_name,
+            barrel_name: row.barrel_name,
+            ammo_type_name: row.ammo_type_name,
+            effective_range: row.effective_range.into(),
+            damage: row.damage.to_string(),
+            velocity: row.velocity.into(),
+            rpm_single: row.rpm_single.map(i32::from),
+            rpm_burst: row.rpm_burst.map(i32::from),
+            rpm_auto: row.rpm_auto.map(i32::from),
+            headshot_multiplier: row.headshot_multiplier.map(|value| value.to_string()),
+            upper_chest_multiplier: row.upper_chest_multiplier.map(|value| value.to_string()),
+            limb_multiplier: row.limb_multiplier.map(|value| value.to_string()),
+            pellet_count: row.pellet_count.map(i32::from),
+        }
+    }
+}
+
+impl TryFrom<proto::ConfigDamageAtRange> for DamageAtRange {
+    type Error = StatsError;
+
+    fn try_from(message: proto::ConfigDamageAtRange) -> Result<Self> {
+        Ok(Self {
+            weapon_name: message.weapon_name,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            effective_range: small(message.effective_range)?,
+            damage: decimal(&message.damage)?,
+            velocity: small(message.velocity)?,
+            rpm_single: optional_small(message.rpm_single)?,
+            rpm_burst: optional_small(message.rpm_burst)?,
+            rpm_auto: optional_small(message.rpm_auto)?,
+            headshot_multiplier: optional_decimal(message.headshot_multiplier)?,
+            upper_chest_multiplier: optional_decimal(message.upper_chest_multiplier)?,
+            limb_multiplier: optional_decimal(message.limb_multiplier)?,
+            pellet_count: optional_small(message.pellet_count)?,
+            flight_time_ms: None,
+            bullet_drop: None,
+            lead_distance: None,
+            effective_ttk_ms: None,
+        })
+    }
+}
+
+impl From<BestConfigInCategory> for proto::BestConfigInCategory {
+    fn from(row: BestConfigInCategory) -> Self {
+        Self {
+            config_id: row.config_id,
+            category_name: row.category_name,
+            weapon_name: row.weapon_name,
+            barrel_name: row.barrel_name,
+            ammo_type_name: row.ammo_type_name,
+            effective_range: row.effective_range.into(),
+            damage: row.damage.to_string(),
+            velocity: row.velocity.into(),
+            rpm_single: row.rpm_single.map(i32::from),
+            rpm_burst: row.rpm_burst.map(i32::from),
+            rpm_auto: row.rpm_auto.map(i32::from),
+            magazine_size: row.magazine_size.into(),
+            empty_reload_time: row.empty_reload_time.map(|value| value.to_string()),
+            tactical_reload_time: row.tactical_reload_time.map(|value| value.to_string()),
+            headshot_multiplier: row.headshot_multiplier.to_string(),
+            recoil_vertical: row.recoil_vertical.map(|value| value.to_string()),
+            recoil_horizontal: row.recoil_horizontal.map(|value| value.to_string()),
+            recoil_first_shot_multiplier: row
+                .recoil_first_shot_multiplier
+                .map(|value| value.to_string()),
+            spread_ads: row.spread_ads.map(|value| value.to_string()),
+            spread_hipfire: row.spread_hipfire.map(|value| value.to_string()),
+            spread_bloom_per_shot: row.spread_bloom_per_shot.map(|value| value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<proto::BestConfigInCategory> for BestConfigInCategory {
+    type Error = StatsError;
+
+    fn try_from(message: proto::BestConfigInCategory) -> Result<Self> {
+        Ok(Self {
+            config_id: message.config_id,
+            category_name: message.category_name,
+            weapon_name: message.weapon_name,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            effective_range: small(message.effective_range)?,
+            damage: decimal(&message.damage)?,
+            velocity: small(message.velocity)?,
+            rpm_single: optional_small(message.rpm_single)?,
+            rpm_burst: optional_small(message.rpm_burst)?,
+            rpm_auto: optional_small(message.rpm_auto)?,
+            magazine_size: small(message.magazine_size)?,
+            empty_reload_time: optional_decimal(message.empty_reload_time)?,
+            tactical_reload_time: optional_decimal(message.tactical_reload_time)?,
+            headshot_multiplier: decimal(&message.headshot_multiplier)?,
+            recoil_vertical: optional_decimal(message.recoil_vertical)?,
+            recoil_horizontal: optional_decimal(message.recoil_horizontal)?,
+            recoil_first_shot_multiplier: optional_decimal(message.recoil_first_shot_multiplier)?,
+            spread_ads: optional_decimal(message.spread_ads)?,
+            spread_hipfire: optional_decimal(message.spread_hipfire)?,
+            spread_bloom_per_shot: optional_decimal(message.spread_bloom_per_shot)?,
+        })
+    }
+}
+
+impl From<BarrelWithCount> for proto::Barrel {
+    fn from(row: BarrelWithCount) -> Self {
+        Self {
+            barrel_id: row.barrel_id,
+            barrel_name: row.barrel_name,
+            config_count: row.config_count,
+        }
+    }
+}
+
+impl TryFrom<proto::Barrel> for BarrelWithCount {
+    type Error = StatsError;
+
+    fn try_from(message: proto::Barrel) -> Result<Self> {
+        Ok(Self {
+            barrel_id: message.barrel_id,
+            barrel_name: message.barrel_name,
+            config_count: message.config_count,
+        })
+    }
+}
+
+impl From<AmmoTypeWithCount> for proto::AmmoType {
+    fn from(row: AmmoTypeWithCount) -> Self {
+        Self {
+            ammo_id: row.ammo_id,
+            ammo_type_name: row.ammo_type_name,
+            config_count: row.config_count,
+        }
+    }
+}
+
+impl TryFrom<proto::AmmoType> for AmmoTypeWithCount {
+    type Error = StatsError;
+
+    fn try_from(message: proto::AmmoType) -> Result<Self> {
+        Ok(Self {
+            ammo_id: message.ammo_id,
+            ammo_type_name: message.ammo_type_name,
+            config_count: message.config_count,
+        })
+    }
+}
+
+impl From<WeaponWithCategory> for proto::WeaponWithCategory {
+    fn from(row: WeaponWithCategory) -> Self {
+        Self {
+            weapon_id: row.weapon_id,
+            weapon_name: row.weapon_name,
+            category_id: row.category_id,
+            category_name: row.category_name,
+            config_count: row.config_count,
+        }
+    }
+}
+
+impl TryFrom<proto::WeaponWithCategory> for WeaponWithCategory {
+    type Error = StatsError;
+
+    fn try_from(message: proto::WeaponWithCategory) -> Result<Self> {
+        Ok(Self {
+            weapon_id: message.weapon_id,
+            weapon_name: message.weapon_name,
+            category_id: message.category_id,
+            category_name: message.category_name,
+            config_count: message.config_count,
+        })
+    }
+}
+
+impl From<TargetProfile> for proto::TargetProfile {
+    fn from(profile: TargetProfile) -> Self {
+        Self {
+            profile_name: profile.profile_name,
+            base_health: profile.base_health.into(),
+            armor_health: profile.armor_health.into(),
+            armor_damage_reduction: profile.armor_damage_reduction.to_string(),
+            limb_multiplier: profile.limb_multiplier.to_string(),
+            headshot_multiplier_override: profile
+                .headshot_multiplier_override
+                .map(|value| value.to_string()),
+        }
+    }
+}
+
+impl TryFrom<proto::TargetProfile> for TargetProfile {
+    type Error = StatsError;
+
+    fn try_from(message: proto::TargetProfile) -> Result<Self> {
+        Ok(Self {
+            profile_name: message.profile_name,
+            base_health: small(message.base_health)?,
+            armor_health: small(message.armor_health)?,
+            armor_damage_reduction: decimal(&message.armor_damage_reduction)?,
+            limb_multiplier: decimal(&message.limb_multiplier)?,
+            headshot_multiplier_override: optional_decimal(message.headshot_multiplier_override)?,
+        })
+    }
+}
+
+impl From<DamageDropoff> for proto::DamageDropoff {
+    fn from(row: DamageDropoff) -> Self {
+        Self {
+            range: row.range.into(),
+            damage: row.damage.to_string(),
+        }
+    }
+}
+
+impl TryFrom<proto::DamageDropoff> for DamageDropoff {
+    type Error = StatsError;
+
+    fn try_from(message: proto::DamageDropoff) -> Result<Self> {
+        Ok(Self {
+            range: small(message.range)?,
+            damage: decimal(&message.damage)?,
+        })
+    }
+}
+
+impl From<WeaponConfig> for proto::WeaponConfig {
+    fn from(config: WeaponConfig) -> Self {
+        Self {
+            config_id: config.config_id,
+            weapon_name: config.weapon_name,
+            barrel_name: config.barrel_name,
+            ammo_type_name: config.ammo_type_name,
+            velocity: config.velocity.into(),
+            rpm_single: config.rpm_single.map(i32::from),
+            rpm_burst: config.rpm_burst.map(i32::from),
+            rpm_auto: config.rpm_auto.map(i32::from),
+            recoil_vertical: config.recoil_vertical.map(|value| value.to_string()),
+            recoil_horizontal: config.recoil_horizontal.map(|value| value.to_string()),
+            recoil_first_shot_multiplier: config
+                .recoil_first_shot_multiplier
+                .map(|value| value.to_string()),
+            spread_ads: config.spread_ads.map(|value| value.to_string()),
+            spread_hipfire: config.spread_hipfire.map(|value| value.to_string()),
+            spread_bloom_per_shot: config.spread_bloom_per_shot.map(|value| value.to_string()),
+            dropoffs: config.dropoffs.into_iter().map(Into::into).collect(),
+            ammo_stats: config.ammo_stats.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<proto::WeaponConfig> for WeaponConfig {
+    type Error = StatsError;
+
+    fn try_from(message: proto::WeaponConfig) -> Result<Self> {
+        Ok(Self {
+            config_id: message.config_id,
+            weapon_name: message.weapon_name,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            velocity: small(message.velocity)?,
+            rpm_single: optional_small(message.rpm_single)?,
+            rpm_burst: optional_small(message.rpm_burst)?,
+            rpm_auto: optional_small(message.rpm_auto)?,
+            recoil_vertical: optional_decimal(message.recoil_vertical)?,
+            recoil_horizontal: optional_decimal(message.recoil_horizontal)?,
+            recoil_first_shot_multiplier: optional_decimal(message.recoil_first_shot_multiplier)?,
+            spread_ads: optional_decimal(message.spread_ads)?,
+            spread_hipfire: optional_decimal(message.spread_hipfire)?,
+            spread_bloom_per_shot: optional_decimal(message.spread_bloom_per_shot)?,
+            dropoffs: message
+                .dropoffs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+            ammo_stats: message.ammo_stats.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
+
+impl From<WeaponMetrics> for proto::WeaponMetrics {
+    fn from(metrics: WeaponMetrics) -> Self {
+        Self {
+            configuration_count: metrics.configuration_count as u64,
+            max_damage: metrics.max_damage.map(|value| value.to_string()),
+            min_damage: metrics.min_damage.map(|value| value.to_string()),
+            max_velocity: metrics.max_velocity.map(i32::from),
+            max_fire_rate: metrics.max_fire_rate.map(i32::from),
+            max_magazine_size: metrics.max_magazine_size.map(i32::from),
+            best_ttk_ms: metrics.best_ttk_ms,
+        }
+    }
+}
+
+impl TryFrom<proto::WeaponMetrics> for WeaponMetrics {
+    type Error = StatsError;
+
+    fn try_from(message: proto::WeaponMetrics) -> Result<Self> {
+        Ok(Self {
+            configuration_count: message.configuration_count as usize,
+            max_damage: optional_decimal(message.max_damage)?,
+            min_damage: optional_decimal(message.min_damage)?,
+            max_velocity: optional_small(message.max_velocity)?,
+            max_fire_rate: optional_small(message.max_fire_rate)?,
+            max_magazine_size: optional_small(message.max_magazine_size)?,
+            best_ttk_ms: message.best_ttk_ms,
+        })
+    }
+}
+
+impl From<WeaponDetails> for proto::WeaponDetails {
+    fn from(details: WeaponDetails) -> Self {
+        Self {
+            weapon: Some(details.weapon.into()),
+            category_name: details.category_name,
+            configurations: details.configurations.into_iter().map(Into::into).collect(),
+            ammo_stats: details.ammo_stats.into_iter().map(Into::into).collect(),
+            metrics: Some(details.metrics.into()),
+        }
+    }
+}
+
+impl TryFrom<proto::WeaponDetails> for WeaponDetails {
+    type Error = StatsError;
+
+    fn try_from(message: proto::WeaponDetails) -> Result<Self> {
+        Ok(Self {
+            weapon: required(message.weapon, "weapon")?.try_into()?,
+            category_name: message.category_name,
+            configurations: message
+                .configurations
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+            ammo_stats: message
+                .ammo_stats
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+            metrics: required(message.metrics, "metrics")?.try_into()?,
+        })
+    }
+}
+
+impl From<ConfigSort> for proto::ConfigSort {
+    fn from(sort: ConfigSort) -> Self {
+        match sort {
+            ConfigSort::Damage => proto::ConfigSort::Damage,
+            ConfigSort::Velocity => proto::ConfigSort::Velocity,
+            ConfigSort::FireRate => proto::ConfigSort::FireRate,
+            ConfigSort::MagazineSize => proto::ConfigSort::MagazineSize,
+            ConfigSort::ReloadTime => proto::ConfigSort::ReloadTime,
+        }
+    }
+}
+
+impl From<proto::ConfigSort> for ConfigSort {
+    fn from(sort: proto::ConfigSort) -> Self {
+        match sort {
+            proto::ConfigSort::Damage => ConfigSort::Damage,
+            proto::ConfigSort::Velocity => ConfigSort::Velocity,
+            proto::ConfigSort::FireRate => ConfigSort::FireRate,
+            proto::ConfigSort::MagazineSize => ConfigSort::MagazineSize,
+            proto::ConfigSort::ReloadTime => ConfigSort::ReloadTime,
+        }
+    }
+}
+
+impl From<SortOrder> for proto::SortOrder {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Asc => proto::SortOrder::Asc,
+            SortOrder::Desc => proto::SortOrder::Desc,
+        }
+    }
+}
+
+impl From<proto::SortOrder> for SortOrder {
+    fn from(order: proto::SortOrder) -> Self {
+        match order {
+            proto::SortOrder::Asc => SortOrder::Asc,
+            proto::SortOrder::Desc => SortOrder::Desc,
+        }
+    }
+}
+
+impl From<ConfigQuery> for proto::ConfigQuery {
+    fn from(query: ConfigQuery) -> Self {
+        Self {
+            category: query.category,
+            weapon: query.weapon,
+            barrel: query.barrel,
+            ammo: query.ammo,
+            min_velocity: query.min_velocity.map(i32::from),
+            max_velocity: query.max_velocity.map(i32::from),
+            min_rpm: query.min_rpm.map(i32::from),
+            max_rpm: query.max_rpm.map(i32::from),
+            min_magazine: query.min_magazine.map(i32::from),
+            max_magazine: query.max_magazine.map(i32::from),
+            max_reload: query.max_reload.map(|value| value.to_string()),
+            range: query.range.into(),
+            min_damage: query.min_damage.map(|value| value.to_string()),
+            max_damage: query.max_damage.map(|value| value.to_string()),
+            sort: proto::ConfigSort::from(query.sort).into(),
+            order: proto::SortOrder::from(query.order).into(),
+            limit: query.limit,
+            offset: query.offset,
+        }
+    }
+}
+
+impl TryFrom<proto::ConfigQuery> for ConfigQuery {
+    type Error = StatsError;
+
+    fn try_from(message: proto::ConfigQuery) -> Result<Self> {
+        Ok(Self {
+            category: message.category,
+            weapon: message.weapon,
+            barrel: message.barrel,
+            ammo: message.ammo,
+            min_velocity: optional_small(message.min_velocity)?,
+            max_velocity: optional_small(message.max_velocity)?,
+            min_rpm: optional_small(message.min_rpm)?,
+            max_rpm: optional_small(message.max_rpm)?,
+            min_magazine: optional_small(message.min_magazine)?,
+            max_magazine: optional_small(message.max_magazine)?,
+            max_reload: optional_decimal(message.max_reload)?,
+            range: small(message.range)?,
+            min_damage: optional_decimal(message.min_damage)?,
+            max_damage: optional_decimal(message.max_damage)?,
+            sort: enumeration::<proto::ConfigSort>(message.sort)?.into(),
+            order: enumeration::<proto::SortOrder>(message.order)?.into(),
+            limit: message.limit,
+            offset: message.offset,
+        })
+    }
+}
+
+impl From<RankBy> for proto::RankBy {
+    fn from(rank_by: RankBy) -> Self {
+        match rank_by {
+            RankBy::Ttk => proto::RankBy::Ttk,
+            RankBy::Damage => proto::RankBy::Damage,
+        }
+    }
+}
+
+impl From<proto::RankBy> for RankBy {
+    fn from(rank_by: proto::RankBy) -> Self {
+        match rank_by {
+            proto::RankBy::Ttk => RankBy::Ttk,
+            proto::RankBy::Damage => RankBy::Damage,
+        }
+    }
+}
+
+impl From<RangeBandWinner> for proto::RangeBandWinner {
+    fn from(row: RangeBandWinner) -> Self {
+        Self {
+            band_start: row.band_start.into(),
+            band_end: row.band_end.map(i32::from),
+            rank: row.rank,
+            category_name: row.category_name,
+            weapon_name: row.weapon_name,
+            barrel_name: row.barrel_name,
+            ammo_type_name: row.ammo_type_name,
+            effective_range: row.effective_range.into(),
+            damage: row.damage.to_string(),
+            pellet_count: row.pellet_count.map(i32::from),
+            bullets_to_kill: row.bullets_to_kill,
+            ttk_ms: row.ttk_ms,
+            velocity: row.velocity.into(),
+            rpm_single: row.rpm_single.map(i32::from),
+            rpm_burst: row.rpm_burst.map(i32::from),
+            rpm_auto: row.rpm_auto.map(i32::from),
+        }
+    }
+}
+
+impl TryFrom<proto::RangeBandWinner> for RangeBandWinner {
+    type Error = StatsError;
+
+    fn try_from(message: proto::RangeBandWinner) -> Result<Self> {
+        Ok(Self {
+            band_start: small(message.band_start)?,
+            band_end: optional_small(message.band_end)?,
+            rank: message.rank,
+            category_name: message.category_name,
+            weapon_name: message.weapon_name,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            effective_range: small(message.effective_range)?,
+            damage: decimal(&message.damage)?,
+            pellet_count: optional_small(message.pellet_count)?,
+            bullets_to_kill: message.bullets_to_kill,
+            ttk_ms: message.ttk_ms,
+            velocity: small(message.velocity)?,
+            rpm_single: optional_small(message.rpm_single)?,
+            rpm_burst: optional_small(message.rpm_burst)?,
+            rpm_auto: optional_small(message.rpm_auto)?,
+        })
+    }
+}
+
+impl From<Side> for proto::Side {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::A => proto::Side::A,
+            Side::B => proto::Side::B,
+        }
+    }
+}
+
+impl From<proto::Side> for Side {
+    fn from(side: proto::Side) -> Self {
+        match side {
+            proto::Side::A => Side::A,
+            proto::Side::B => Side::B,
+        }
+    }
+}
+
+impl From<ComparedConfig> for proto::ComparedConfig {
+    fn from(config: ComparedConfig) -> Self {
+        Self {
+            config_id: config.config_id,
+            weapon_name: config.weapon_name,
+            barrel_name: config.barrel_name,
+            ammo_type_name: config.ammo_type_name,
+            velocity: config.velocity.into(),
+            fire_rate: config.fire_rate.map(i32::from),
+        }
+    }
+}
+
+impl TryFrom<proto::ComparedConfig> for ComparedConfig {
+    type Error = StatsError;
+
+    fn try_from(message: proto::ComparedConfig) -> Result<Self> {
+        Ok(Self {
+            config_id: message.config_id,
+            weapon_name: message.weapon_name,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            velocity: small(message.velocity)?,
+            fire_rate: optional_small(message.fire_rate)?,
+        })
+    }
+}
+
+impl From<RangeComparison> for proto::RangeComparison {
+    fn from(row: RangeComparison) -> Self {
+        Self {
+            range: row.range.into(),
+            damage_a: row.damage_a.map(|value| value.to_string()),
+            damage_b: row.damage_b.map(|value| value.to_string()),
+            bullets_to_kill_a: row.bullets_to_kill_a,
+            bullets_to_kill_b: row.bullets_to_kill_b,
+            ttk_ms_a: row.ttk_ms_a,
+            ttk_ms_b: row.ttk_ms_b,
+            advantage_ms: row.advantage_ms,
+            winner: row.winner.map(|side| proto::Side::from(side).into()),
+        }
+    }
+}
+
+impl TryFrom<proto::RangeComparison> for RangeComparison {
+    type Error = StatsError;
+
+    fn try_from(message: proto::RangeComparison) -> Result<Self> {
+        Ok(Self {
+            range: small(message.range)?,
+            damage_a: optional_decimal(message.damage_a)?,
+            damage_b: optional_decimal(message.damage_b)?,
+            bullets_to_kill_a: message.bullets_to_kill_a,
+            bullets_to_kill_b: message.bullets_to_kill_b,
+            ttk_ms_a: message.ttk_ms_a,
+            ttk_ms_b: message.ttk_ms_b,
+            advantage_ms: message.advantage_ms,
+            winner: message
+                .winner
+                .map(enumeration::<proto::Side>)
+                .transpose()?
+                .map(Side::from),
+        })
+    }
+}
+
+impl From<Crossover> for proto::Crossover {
+    fn from(crossover: Crossover) -> Self {
+        Self {
+            from_range: crossover.from_range.into(),
+            to_range: crossover.to_range.into(),
+            winner: proto::Side::from(crossover.winner).into(),
+        }
+    }
+}
+
+impl TryFrom<proto::Crossover> for Crossover {
+    type Error = StatsError;
+
+    fn try_from(message: proto::Crossover) -> Result<Self> {
+        Ok(Self {
+            from_range: small(message.from_range)?,
+            to_range: small(message.to_range)?,
+            winner: enumeration::<proto::Side>(message.winner)?.into(),
+        })
+    }
+}
+
+impl From<Comparison> for proto::Comparison {
+    fn from(comparison: Comparison) -> Self {
+        Self {
+            config_a: Some(comparison.config_a.into()),
+            config_b: Some(comparison.config_b.into()),
+            ranges: comparison.ranges.into_iter().map(Into::into).collect(),
+            crossovers: comparison.crossovers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::Comparison> for Comparison {
+    type Error = StatsError;
+
+    fn try_from(message: proto::Comparison) -> Result<Self> {
+        Ok(Self {
+            config_a: required(message.config_a, "config_a")?.try_into()?,
+            config_b: required(message.config_b, "config_b")?.try_into()?,
+            ranges: message
+                .ranges
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+            crossovers: message
+                .crossovers
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl From<ParetoMetric> for proto::ParetoMetric {
+    fn from(metric: ParetoMetric) -> Self {
+        let (kind, range) = match metric {
+            ParetoMetric::TtkAt(range) => (proto::ParetoMetricKind::TtkAt, range),
+            ParetoMetric::DamageAt(range) => (proto::ParetoMetricKind::DamageAt, range),
+            ParetoMetric::MagazineSize => (proto::ParetoMetricKind::MagazineSize, 0),
+            ParetoMetric::Velocity => (proto::ParetoMetricKind::Velocity, 0),
+            ParetoMetric::FireRate => (proto::ParetoMetricKind::FireRate, 0),
+            ParetoMetric::ReloadTime => (proto::ParetoMetricKind::ReloadTime, 0),
+        };
+        Self {
+            kind: kind.into(),
+            range: range.into(),
+        }
+    }
+}
+
+impl TryFrom<proto::ParetoMetric> for ParetoMetric {
+    type Error = StatsError;
+
+    fn try_from(message: proto::ParetoMetric) -> Result<Self> {
+        Ok(
+            match enumeration::<proto::ParetoMetricKind>(message.kind)? {
+                proto::ParetoMetricKind::TtkAt => ParetoMetric::TtkAt(small(message.range)?),
+                proto::ParetoMetricKind::DamageAt => ParetoMetric::DamageAt(small(message.range)?),
+                proto::ParetoMetricKind::MagazineSize => ParetoMetric::MagazineSize,
+                proto::ParetoMetricKind::Velocity => ParetoMetric::Velocity,
+                proto::ParetoMetricKind::FireRate => ParetoMetric::FireRate,
+                proto::ParetoMetricKind::ReloadTime => ParetoMetric::ReloadTime,
+            },
+        )
+    }
+}
+
+impl From<ParetoConfig> for proto::ParetoConfig {
+    fn from(config: ParetoConfig) -> Self {
+        Self {
+            config_id: config.config_id,
+            barrel_name: config.barrel_name,
+            ammo_type_name: config.ammo_type_name,
+            values: config
+                .values
+                .into_iter()
+                .map(|value| proto::MetricValue { value })
+                .collect(),
+            wins: config.wins.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::ParetoConfig> for ParetoConfig {
+    type Error = StatsError;
+
+    fn try_from(message: proto::ParetoConfig) -> Result<Self> {
+        Ok(Self {
+            config_id: message.config_id,
+            barrel_name: message.barrel_name,
+            ammo_type_name: message.ammo_type_name,
+            values: message
+                .values
+                .into_iter()
+                .map(|value| value.value)
+                .collect(),
+            wins: message
+                .wins
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+/// Stream of response messages
+type ResponseStream<T> = BoxStream<'static, std::result::Result<T, Status>>;
+
+/// Respond with a client stream converted to messages
+fn stream_response<A, R, M, F>(
+    client: &Arc<StatsClient>,
+    args: A,
+    query: F,
+) -> Response<ResponseStream<M>>
+where
+    A: Send + Sync + 'static,
+    R: Into<M> + Send + 'static,
+    M: Send + 'static,
+    F: for<'a> FnOnce(&'a StatsClient, &'a A) -> BoxStream<'a, Result<R>> + Send + 'static,
+{
+    let messages = client
+        .spawn_stream(args, query)
+        .map(|row| row.map(Into::into).map_err(status));
+    Response::new(messages.boxed())
+}
+
+/// `StatsService` implementation backed by a shared `StatsClient`
+pub struct StatsGrpcService {
+    client: Arc<StatsClient>,
+}
+
+impl StatsGrpcService {
+    pub fn new(client: Arc<StatsClient>) -> Self {
+        Self { client }
+    }
+}
+
+/// Service ready to be added to a `tonic::transport::Server`
+pub fn server(client: Arc<StatsClient>) -> StatsServiceServer<StatsGrpcService> {
+    StatsServiceServer::new(StatsGrpcService::new(client))
+}
+
+#[tonic::async_trait]
+impl StatsService for StatsGrpcService {
+    type CategoriesStream = ResponseStream<proto::Category>;
+    type WeaponsByCategoryStream = ResponseStream<proto::Weapon>;
+    type WeaponConfigsStream = ResponseStream<proto::WeaponConfigWithDropoffs>;
+    type WeaponAmmoStatsStream = ResponseStream<proto::WeaponAmmoStatsWithNames>;
+    type DamageAtRangeStream = ResponseStream<proto::ConfigDamageAtRange>;
+    type BestConfigsInCategoryStream = ResponseStream<proto::BestConfigInCategory>;
+    type BarrelsStream = ResponseStream<proto::Barrel>;
+    type AmmoTypesStream = ResponseStream<proto::AmmoType>;
+    type AllWeaponsStream = ResponseStream<proto::WeaponWithCategory>;
+    type TargetProfilesStream = ResponseStream<proto::TargetProfile>;
+    type QueryConfigsStream = ResponseStream<proto::BestConfigInCategory>;
+    type RangeBandWinnersStream = ResponseStream<proto::RangeBandWinner>;
+
+    async fn categories(
+        &self,
+        _request: Request<proto::CategoriesRequest>,
+    ) -> std::result::Result<Response<Self::CategoriesStream>, Status> {
+        Ok(stream_response(&self.client, (), |client, _| {
+            client.categories().boxed()
+        }))
+    }
+
+    async fn weapons_by_category(
+        &self,
+        request: Request<proto::CategoryRequest>,
+    ) -> std::result::Result<Response<Self::WeaponsByCategoryStream>, Status> {
+        let category_name = request.into_inner().category_name;
+        self.client.category(&category_name).await.map_err(status)?;
+
+        Ok(stream_response(
+            &self.client,
+            category_name,
+            |client, name| client.weapons_by_category(name).boxed(),
+        ))
+    }
+
+    async fn get_weapon(
+        &self,
+        request: Request<proto::WeaponRequest>,
+    ) -> std::result::Result<Response<proto::Weapon>, Status> {
+        let weapon = self
+            .client
+            .weapon(&request.into_inner().weapon_name)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(weapon.into()))
+    }
+
+    async fn weapon_configs(
+        &self,
+        request: Request<proto::WeaponRequest>,
+    ) -> std::result::Result<Response<Self::WeaponConfigsStream>, Status> {
+        let weapon_name = request.into_inner().weapon_name;
+        self.client.weapon(&weapon_name).await.map_err(status)?;
+
+        Ok(stream_response(
+            &self.client,
+            weapon_name,
+            |client, name| client.weapon_configs(name).boxed(),
+        ))
+    }
+
+    async fn weapon_ammo_stats(
+        &self,
+        request: Request<proto::WeaponRequest>,
+    ) -> std::result::Result<Response<Self::WeaponAmmoStatsStream>, Status> {
+        let weapon_name = request.into_inner().weapon_name;
+        self.client.weapon(&weapon_name).await.map_err(status)?;
+
+        Ok(stream_response(
+            &self.client,
+            weapon_name,
+            |client, name| client.weapon_ammo_stats(name).boxed(),
+        ))
+    }
+
+    async fn damage_at_range(
+        &self,
+        request: Request<proto::DamageAtRangeRequest>,
+    ) -> std::result::Result<Response<Self::DamageAtRangeStream>, Status> {
+        let request = request.into_inner();
+        let range = range_arg(request.range)?;
+        self.client
+            .weapon(&request.weapon_name)
+            .await
+            .map_err(status)?;
+
+        Ok(stream_response(
+            &self.client,
+            request.weapon_name,
+            move |client, name| client.damage_at_range(name, range).boxed(),
+        ))
+    }
+
+    async fn best_configs_in_category(
+        &self,
+        request: Request<proto::BestConfigsInCategoryRequest>,
+    ) -> std::result::Result<Response<Self::BestConfigsInCategoryStream>, Status> {
+        let request = request.into_inner();
+        let range = range_arg(request.range)?;
+        if request.limit < 0 {
+            return Err(Status::invalid_argument(format!(
+                "Limit must not be negative, got {}",
+                request.limit
+            )));
+        }
+        self.client
+            .category(&request.category_name)
+            .await
+            .map_err(status)?;
+
+        let limit = request.limit;
+        Ok(stream_response(
+            &self.client,
+            request.category_name,
+            move |client, name| client.best_configs_in_category(name, range, limit).boxed(),
+        ))
+    }
+
+    async fn barrels(
+        &self,
+        _request: Request<proto::BarrelsRequest>,
+    ) -> std::result::Result<Response<Self::BarrelsStream>, Status> {
+        Ok(stream_response(&self.client, (), |client, _| {
+            client.barrels().boxed()
+        }))
+    }
+
+    async fn ammo_types(
+        &self,
+        _request: Request<proto::AmmoTypesRequest>,
+    ) -> std::result::Result<Response<Self::AmmoTypesStream>, Status> {
+        Ok(stream_response(&self.client, (), |client, _| {
+            client.ammo_types().boxed()
+        }))
+    }
+
+    async fn all_weapons(
+        &self,
+        _request: Request<proto::AllWeaponsRequest>,
+    ) -> std::result::Result<Response<Self::AllWeaponsStream>, Status> {
+        Ok(stream_response(&self.client, (), |client, _| {
+            client.all_weapons().boxed()
+        }))
+    }
+
+    async fn target_profiles(
+        &self,
+        _request: Request<proto::TargetProfilesRequest>,
+    ) -> std::result::Result<Response<Self::TargetProfilesStream>, Status> {
+        Ok(stream_response(&self.client, (), |client, _| {
+            client.target_profiles().boxed()
+        }))
+    }
+
+    async fn get_target_profile(
+        &self,
+        request: Request<proto::TargetProfileRequest>,
+    ) -> std::result::Result<Response<proto::TargetProfile>, Status> {
+        let profile = self
+            .client
+            .target_profile(&request.into_inner().profile_name)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(profile.into()))
+    }
+
+    async fn weapon_details_aggregated(
+        &self,
+        request: Request<proto::WeaponRequest>,
+    ) -> std::result::Result<Response<proto::WeaponDetails>, Status> {
+        let details = self
+            .client
+            .weapon_details_aggregated(&request.into_inner().weapon_name)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(details.into()))
+    }
+
+    async fn query_configs(
+        &self,
+        request: Request<proto::ConfigQuery>,
+    ) -> std::result::Result<Response<Self::QueryConfigsStream>, Status> {
+        let query = ConfigQuery::try_from(request.into_inner()).map_err(status)?;
+
+        Ok(stream_response(&self.client, query, |client, query| {
+            client.query_configs(query).boxed()
+        }))
+    }
+
+    async fn range_band_winners(
+        &self,
+        request: Request<proto::RangeBandWinnersRequest>,
+    ) -> std::result::Result<Response<Self::RangeBandWinnersStream>, Status> {
+        let request = request.into_inner();
+        let query = RangeBandQuery {
+            band_width: range_arg(request.band_width)?,
+            open_band_start: range_arg(request.open_band_start)?,
+            rank_by: enumeration::<proto::RankBy>(request.rank_by)
+                .map_err(status)?
+                .into(),
+            limit: request.limit,
+            target: target_arg(request.target)?,
+        };
+
+        Ok(stream_response(&self.client, query, |client, query| {
+            client.range_band_winners(query).boxed()
+        }))
+    }
+
+    async fn compare(
+        &self,
+        request: Request<proto::CompareRequest>,
+    ) -> std::result::Result<Response<proto::Comparison>, Status> {
+        let request = request.into_inner();
+        let ranges = request
+            .ranges
+            .into_iter()
+            .map(range_arg)
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let target = target_arg(request.target)?;
+
+        let comparison = self
+            .client
+            .compare(request.config_a, request.config_b, &ranges, &target)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(comparison.into()))
+    }
+
+    async fn pareto_configs(
+        &self,
+        request: Request<proto::ParetoConfigsRequest>,
+    ) -> std::result::Result<Response<proto::ParetoFrontier>, Status> {
+        let request = request.into_inner();
+        let metrics = request
+            .metrics
+            .into_iter()
+            .map(ParetoMetric::try_from)
+            .collect::<Result<Vec<_>>>()
+            .map_err(status)?;
+        let target = target_arg(request.target)?;
+
+        let configs = self
+            .client
+            .pareto_configs(&request.weapon_name, &metrics, &target)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(proto::ParetoFrontier {
+            configs: configs.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+/// Client of a remote `StatsService`, returning the same model types as `StatsClient`
+#[derive(Debug, Clone)]
+pub struct GrpcStatsClient {
+    inner: StatsServiceClient<Channel>,
+}
+
+/// Convert a streaming response into model items
+fn items<M, T>(
+    response: Response<tonic::Streaming<M>>,
+) -> impl Stream<Item = Result<T>> + Send + 'static
+where
+    M: Send + 'static,
+    T: TryFrom<M, Error = StatsError> + Send + 'static,
+{
+    response
+        .into_inner()
+        .map(|message| message.map_err(stats_error).and_then(T::try_from))
+}
+
+impl GrpcStatsClient {
+    /// Connect to a server, e.g. `http://127.0.0.1:50051`
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self> {
+        let endpoint = endpoint.into();
+        let inner = StatsServiceClient::connect(endpoint.clone())
+            .await
+            .map_err(|err| {
+                StatsError::QueryFailed(format!("gRPC connection to {} failed: {}", endpoint, err))
+            })?;
+        Ok(Self { inner })
+    }
+
+    /// Use an existing channel
+    pub fn new(channel: Channel) -> Self {
+        Self {
+            inner: StatsServiceClient::new(channel),
+        }
+    }
+
+    /// Get all categories with their weapon counts
+    pub async fn categories(&self) -> Result<impl Stream<Item = Result<CategoryWithCount>>> {
+        let response = self
+            .inner
+            .clone()
+            .categories(proto::CategoriesRequest {})
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get weapons by category
+    pub async fn weapons_by_category(
+        &self,
+        category_name: &str,
+    ) -> Result<impl Stream<Item = Result<Weapon>>> {
+        let response = self
+            .inner
+            .clone()
+            .weapons_by_category(proto::CategoryRequest {
+                category_name: category_name.to_string(),
+            })
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get a weapon by name
+    pub async fn weapon(&self, weapon_name: &str) -> Result<Weapon> {
+        let response = self
+            .inner
+            .clone()
+            .get_weapon(proto::WeaponRequest {
+                weapon_name: weapon_name.to_string(),
+            })
+            .await
+            .map_err(stats_error)?;
+        response.into_inner().try_into()
+    }
+
+    /// Get weapon configurations with damage dropoffs
+    pub async fn weapon_configs(
+        &self,
+        weapon_name: &str,
+    ) -> Result<impl Stream<Item = Result<WeaponConfigWithDropoffs>>> {
+        let response = self
+            .inner
+            .clone()
+            .weapon_configs(proto::WeaponRequest {
+                weapon_name: weapon_name.to_string(),
+            })
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get weapon ammo stats
+    pub async fn weapon_ammo_stats(
+        &self,
+        weapon_name: &str,
+    ) -> Result<impl Stream<Item = Result<WeaponAmmoStatsWithNames>>> {
+        let response = self
+            .inner
+            .clone()
+            .weapon_ammo_stats(proto::WeaponRequest {
+                weapon_name: weapon_name.to_string(),
+            })
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get effective damage for weapon configurations at specific range
+    pub async fn damage_at_range(
+        &self,
+        weapon_name: &str,
+        target_range: i16,
+    ) -> Result<impl Stream<Item = Result<DamageAtRange>>> {
+        let response = self
+            .inner
+            .clone()
+            .damage_at_range(proto::DamageAtRangeRequest {
+                weapon_name: weapon_name.to_string(),
+                range: target_range.into(),
+            })
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get top performing configurations in a category at specific range
+    pub async fn best_configs_in_category(
+        &self,
+        category_name: &str,
+        target_range: i16,
+        limit: i64,
+    ) -> Result<impl Stream<Item = Result<BestConfigInCategory>>> {
+        let response = self
+            .inner
+            .clone()
+            .best_configs_in_category(proto::BestConfigsInCategoryRequest {
+                category_name: category_name.to_string(),
+                range: target_range.into(),
+                limit,
+            })
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get all barrels with the number of configurations using them
+    pub async fn barrels(&self) -> Result<impl Stream<Item = Result<BarrelWithCount>>> {
+        let response = self
+            .inner
+            .clone()
+            .barrels(proto::BarrelsRequest {})
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get all ammo types with the number of configurations using them
+    pub async fn ammo_types(&self) -> Result<impl Stream<Item = Result<AmmoTypeWithCount>>> {
+        let response = self
+            .inner
+            .clone()
+            .ammo_types(proto::AmmoTypesRequest {})
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get all weapons with their category and configuration counts
+    pub async fn all_weapons(&self) -> Result<impl Stream<Item = Result<WeaponWithCategory>>> {
+        let response = self
+            .inner
+            .clone()
+            .all_weapons(proto::AllWeaponsRequest {})
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get all stored target profiles
+    pub async fn target_profiles(&self) -> Result<impl Stream<Item = Result<TargetProfile>>> {
+        let response = self
+            .inner
+            .clone()
+            .target_profiles(proto::TargetProfilesRequest {})
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get a target profile by name
+    pub async fn target_profile(&self, profile_name: &str) -> Result<TargetProfile> {
+        let response = self
+            .inner
+            .clone()
+            .get_target_profile(proto::TargetProfileRequest {
+                profile_name: profile_name.to_string(),
+            })
+            .await
+            .map_err(stats_error)?;
+        response.into_inner().try_into()
+    }
+
+    /// Get a weapon with its configurations, ammo stats and summary metrics
+    pub async fn weapon_details_aggregated(&self, weapon_name: &str) -> Result<WeaponDetails> {
+        let response = self
+            .inner
+            .clone()
+            .weapon_details_aggregated(proto::WeaponRequest {
+                weapon_name: weapon_name.to_string(),
+            })
+            .await
+            .map_err(stats_error)?;
+        response.into_inner().try_into()
+    }
+
+    /// Get configurations matching a `ConfigQuery`
+    pub async fn query_configs(
+        &self,
+        query: &ConfigQuery,
+    ) -> Result<impl Stream<Item = Result<BestConfigInCategory>>> {
+        let response = self
+            .inner
+            .clone()
+            .query_configs(proto::ConfigQuery::from(query.clone()))
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Get the top configurations of each range band across all categories
+    pub async fn range_band_winners(
+        &self,
+        query: &RangeBandQuery,
+    ) -> Result<impl Stream<Item = Result<RangeBandWinner>>> {
+        let response = self
+            .inner
+            .clone()
+            .range_band_winners(proto::RangeBandWinnersRequest {
+                band_width: query.band_width.into(),
+                open_band_start: query.open_band_start.into(),
+                rank_by: proto::RankBy::from(query.rank_by).into(),
+                limit: query.limit,
+                target: Some(query.target.clone().into()),
+            })
+            .await
+            .map_err(stats_error)?;
+        Ok(items(response))
+    }
+
+    /// Compare two configurations head to head at each of the given ranges
+    pub async fn compare(
+        &self,
+        config_a: i32,
+        config_b: i32,
+        ranges: &[i16],
+        target: &TargetProfile,
+    ) -> Result<Comparison> {
+        let response = self
+            .inner
+            .clone()
+            .compare(proto::CompareRequest {
+                config_a,
+                config_b,
+                ranges: ranges.iter().copied().map(i32::from).collect(),
+                target: Some(target.clone().into()),
+            })
+            .await
+            .map_err(stats_error)?;
+        response.into_inner().try_into()
+    }
+
+    /// Get the configurations of a weapon on the pareto frontier of `metrics`
+    pub async fn pareto_configs(
+        &self,
+        weapon_name: &str,
+        metrics: &[ParetoMetric],
+        target: &TargetProfile,
+    ) -> Result<Vec<ParetoConfig>> {
+        let response = self
+            .inner
+            .clone()
+            .pareto_configs(proto::ParetoConfigsRequest {
+                weapon_name: weapon_name.to_string(),
+                metrics: metrics.iter().copied().map(Into::into).collect(),
+                target: Some(target.clone().into()),
+            })
+            .await
+            .map_err(stats_error)?;
+        response
+            .into_inner()
+            .configs
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+}
@@ -0,0 +1,270 @@
+//! JSON HTTP API backed by `StatsClient`
+//!
+//! Collections are streamed as newline-delimited JSON (`application/x-ndjson`),
+//! one object per line, as rows arrive from the database. Single resources are
+//! plain JSON documents. Unknown weapons and categories answer `404 Not Found`.
+//! The OpenAPI 3 description of the API is served at `/openapi.json`.
+
+use crate::models::{
+    BestConfigInCategory, CategoryWithCount, DamageAtRange, WeaponConfig,
+    WeaponConfigWithDropoffs, WeaponDetails,
+};
+use crate::{Result, StatsClient, StatsError};
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+/// Content type of streamed collections
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Number of configurations returned by `/categories/{name}/best` without `limit`
+pub const DEFAULT_BEST_LIMIT: u32 = 10;
+
+/// Build the API router
+///
+/// - `GET /categories`
+/// - `GET /categories/{name}/best?range=&limit=`
+/// - `GET /weapons/{name}`
+/// - `GET /weapons/{name}/configs`
+/// - `GET /weapons/{name}/damage?range=`
+/// - `GET /openapi.json`
+/// - `GET|POST /graphql` with the `graphql` feature
+pub fn router(client: Arc<StatsClient>) -> Router {
+    let router = Router::new()
+        .route("/openapi.json", get(openapi_json))
+        .route("/categories", get(categories))
+        .route("/categories/{name}/best", get(best_configs))
+        .route("/weapons/{name}", get(weapon))
+        .route("/weapons/{name}/configs", get(weapon_configs))
+        .route("/weapons/{name}/damage", get(damage_at_range));
+
+    #[cfg(feature = "graphql")]
+    let router = router.merge(crate::graphql::router(client.clone()));
+
+    router.with_state(client)
+}
+
+/// OpenAPI document of the API
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Battlefield 2042 weapon stats",
+        description = "Collections are streamed as NDJSON, one schema object per line"
+    ),
+    paths(
+        categories,
+        best_configs,
+        weapon,
+        weapon_configs,
+        damage_at_range,
+        openapi_json
+    ),
+    components(schemas(WeaponConfigWithDropoffs)),
+    tags(
+        (name = "categories", description = "Weapon categories"),
+        (name = "weapons", description = "Weapons, configurations and damage")
+    )
+)]
+pub struct ApiDoc;
+
+/// Body of error responses
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ErrorBody {
+    pub error: String,
+}
+
+/// Error response mapped from a `StatsError`
+#[derive(Debug)]
+pub struct ApiError(StatsError);
+
+impl From<StatsError> for ApiError {
+    fn from(err: StatsError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            StatsError::NotFound(_) => StatusCode::NOT_FOUND,
+            _ => {
+                error!("Request failed: {}", self.0);
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let body = ErrorBody {
+            error: self.0.to_string(),
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+/// Stream query results as NDJSON
+///
+/// An error after the first line aborts the response body.
+fn ndjson<A, T, F>(client: Arc<StatsClient>, args: A, query: F) -> Response
+where
+    A: Send + Sync + 'static,
+    T: Serialize + Send + 'static,
+    F: for<'a> FnOnce(&'a StatsClient, &'a A) -> BoxStream<'a, Result<T>> + Send + 'static,
+{
+    let lines = client.spawn_stream(args, query).map(|row| {
+        let mut line = serde_json::to_vec(&row?)?;
+        line.push(b'\n');
+        Ok::<_, StatsError>(line)
+    });
+    let lines = lines.inspect_err(|err| error!("Streaming response failed: {}", err));
+
+    (
+        [(header::CONTENT_TYPE, NDJSON_CONTENT_TYPE)],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// Query parameters of `/weapons/{name}/damage`
+#[derive(Debug, Clone, Copy, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RangeParams {
+    /// Range in meters, 0 by default
+    #[serde(default)]
+    pub range: i16,
+}
+
+/// Query parameters of `/categories/{name}/best`
+#[derive(Debug, Clone, Copy, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BestParams {
+    /// Range in meters, 0 by default
+    #[serde(default)]
+    pub range: i16,
+    /// Maximum number of configurations, 10 by default
+    pub limit: Option<u32>,
+}
+
+/// Categories with their weapon counts
+#[utoipa::path(
+    get,
+    path = "/categories",
+    tag = "categories",
+    responses(
+        (status = 200, description = "One category per line",
+            content_type = "application/x-ndjson", body = CategoryWithCount),
+    )
+)]
+async fn categories(State(client): State<Arc<StatsClient>>) -> Response {
+    ndjson(client, (), |client, _| client.categories().boxed())
+}
+
+/// Configurations in a category with the highest damage at a range
+#[utoipa::path(
+    get,
+    path = "/categories/{name}/best",
+    tag = "categories",
+    params(("name" = String, Path, description = "Category name"), BestParams),
+    responses(
+        (status = 200, description = "One configuration per line, highest damage first",
+            content_type = "application/x-ndjson", body = BestConfigInCategory),
+        (status = 404, description = "Unknown category", body = ErrorBody),
+    )
+)]
+async fn best_configs(
+    State(client): State<Arc<StatsClient>>,
+    Path(name): Path<String>,
+    Query(params): Query<BestParams>,
+) -> std::result::Result<Response, ApiError> {
+    client.category(&name).await?;
+
+    let limit = i64::from(params.limit.unwrap_or(DEFAULT_BEST_LIMIT));
+    Ok(ndjson(client, name, move |client, name| {
+        client
+            .best_configs_in_category(name, params.range, limit)
+            .boxed()
+    }))
+}
+
+/// Weapon with its configurations, ammo stats and summary metrics
+#[utoipa::path(
+    get,
+    path = "/weapons/{name}",
+    tag = "weapons",
+    params(("name" = String, Path, description = "Weapon name")),
+    responses(
+        (status = 200, description = "Weapon details", body = WeaponDetails),
+        (status = 404, description = "Unknown weapon", body = ErrorBody),
+    )
+)]
+async fn weapon(
+    State(client): State<Arc<StatsClient>>,
+    Path(name): Path<String>,
+) -> std::result::Result<Json<WeaponDetails>, ApiError> {
+    Ok(Json(client.weapon_details_aggregated(&name).await?))
+}
+
+/// Configurations of a weapon with their dropoffs and ammo stats
+#[utoipa::path(
+    get,
+    path = "/weapons/{name}/configs",
+    tag = "weapons",
+    params(("name" = String, Path, description = "Weapon name")),
+    responses(
+        (status = 200, description = "One configuration per line",
+            content_type = "application/x-ndjson", body = WeaponConfig),
+        (status = 404, description = "Unknown weapon", body = ErrorBody),
+    )
+)]
+async fn weapon_configs(
+    State(client): State<Arc<StatsClient>>,
+    Path(name): Path<String>,
+) -> std::result::Result<Response, ApiError> {
+    client.weapon(&name).await?;
+
+    Ok(ndjson(client, name, |client, name| {
+        client.weapon_configs_grouped(name).boxed()
+    }))
+}
+
+/// Damage of every configuration of a weapon at a range
+#[utoipa::path(
+    get,
+    path = "/weapons/{name}/damage",
+    tag = "weapons",
+    params(("name" = String, Path, description = "Weapon name"), RangeParams),
+    responses(
+        (status = 200, description = "One configuration per line, highest damage first",
+            content_type = "application/x-ndjson", body = DamageAtRange),
+        (status = 404, description = "Unknown weapon", body = ErrorBody),
+    )
+)]
+async fn damage_at_range(
+    State(client): State<Arc<StatsClient>>,
+    Path(name): Path<String>,
+    Query(params): Query<RangeParams>,
+) -> std::result::Result<Response, ApiError> {
+    client.weapon(&name).await?;
+
+    Ok(ndjson(client, name, move |client, name| {
+        client.damage_at_range(name, params.range).boxed()
+    }))
+}
+
+/// This document
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    responses(
+        (status = 200, description = "OpenAPI 3 document", content_type = "application/json"),
+    )
+)]
+async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
@@ -3,10 +3,29 @@
 //! This library provides access to weapon statistics and damage calculations
 //! for Battlefield 2042, with PostgreSQL backend storage and streaming query support.
 
+pub mod ballistics;
 pub mod client;
+#[cfg(feature = "parquet")]
+pub mod columnar;
+pub mod comparison;
 pub mod database;
+pub mod effects;
 pub mod error;
+#[cfg(feature = "graphql")]
+pub mod graphql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod loadout;
+#[cfg(feature = "mcp")]
+pub mod mcp;
 pub mod models;
+#[cfg(feature = "http")]
+pub mod openapi;
+pub mod pareto;
+pub mod render;
+pub mod simulation;
 
 #[cfg(test)]
 pub mod test_utils;
@@ -0,0 +1,191 @@
+//! Enumeration of full attachment loadouts
+//!
+//! A full loadout picks exactly one attachment for every slot the weapon
+//! has compatible attachments for. Combinations are produced lazily, so
+//! weapons with large catalogs never materialize every loadout at once.
+
+use crate::models::{Attachment, Loadout, Modifier};
+use crate::{Result, StatsError};
+use rust_decimal::prelude::ToPrimitive;
+use std::sync::Arc;
+
+/// Group attachments into slots, preserving slot order of first appearance
+pub fn group_by_slot(attachments: Vec<Attachment>) -> Vec<Vec<Attachment>> {
+    let mut slots: Vec<Vec<Attachment>> = Vec::new();
+    for attachment in attachments {
+        match slots
+            .iter_mut()
+            .find(|slot| slot[0].slot == attachment.slot)
+        {
+            Some(slot) => slot.push(attachment),
+            None => slots.push(vec![attachment]),
+        }
+    }
+    slots
+}
+
+/// Scale a stat by a modifier, rounding to the nearest whole value
+///
+/// Values beyond the range of the stat saturate at its bounds.
+fn scale(value: i16, modifier: Modifier) -> i16 {
+    let scaled = (Modifier::from(value) * modifier).round();
+    scaled.to_i16().unwrap_or(if scaled.is_sign_negative() {
+        i16::MIN
+    } else {
+        i16::MAX
+    })
+}
+
+/// Apply attachments to a bare configuration
+pub fn apply(base: &Loadout, attachments: Vec<Attachment>) -> Loadout {
+    let velocity_modifier: Modifier = attachments.iter().map(|a| a.velocity_modifier).product();
+    let rpm_modifier: Modifier = attachments.iter().map(|a| a.rpm_modifier).product();
+    let spread_modifier: Modifier = attachments.iter().map(|a| a.spread_modifier).product();
+
+    Loadout {
+        velocity: scale(base.velocity, velocity_modifier),
+        rpm_single: base.rpm_single.map(|rpm| scale(rpm, rpm_modifier)),
+        rpm_burst: base.rpm_burst.map(|rpm| scale(rpm, rpm_modifier)),
+        rpm_auto: base.rpm_auto.map(|rpm| scale(rpm, rpm_modifier)),
+        spread_modifier: base.spread_modifier * spread_modifier,
+        attachments,
+        ..base.clone()
+    }
+}
+
+/// Number of full attachment combinations for the given slots
+///
+/// Fails when the count does not fit in a `usize`.
+pub fn combination_count(slots: &[Vec<Attachment>]) -> Result<usize> {
+    slots
+        .iter()
+        .try_fold(1usize, |count, slot| count.checked_mul(slot.len()))
+        .ok_or_else(|| {
+            StatsError::QueryFailed(format!(
+                "Attachment combinations of {} slots overflow",
+                slots.len()
+            ))
+        })
+}
+
+/// Attachments of the `index`-th combination, decoded as a mixed-radix number
+fn combination(slots: &[Vec<Attachment>], mut index: usize) -> Vec<Attachment> {
+    let mut picked = Vec::with_capacity(slots.len());
+    for slot in slots.iter().rev() {
+        picked.push(slot[index % slot.len()].clone());
+        index /= slot.len();
+    }
+    picked.reverse();
+    picked
+}
+
+/// Every full loadout of every configuration
+///
+/// Configurations are the bare loadouts of a weapon, without attachments.
+pub fn enumerate(
+    configs: Vec<Loadout>,
+    attachments: Vec<Attachment>,
+) -> Result<impl Iterator<Item = Loadout>> {
+    let slots = Arc::new(group_by_slot(attachments));
+    let count = combination_count(&slots)?;
+
+    Ok(configs.into_iter().flat_map(move |config| {
+        let slots = Arc::clone(&slots);
+        (0..count).map(move |index| apply(&config, combination(&slots, index)))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn attachment(attachment_id: i32, slot: &str) -> Attachment {
+        Attachment {
+            attachment_id,
+            slot: slot.to_string(),
+            attachment_name: format!("{} {}", slot, attachment_id),
+            velocity_modifier: Decimal::ONE,
+            rpm_modifier: Decimal::ONE,
+            spread_modifier: Decimal::ONE,
+        }
+    }
+
+    fn bare(config_id: i32) -> Loadout {
+        Loadout {
+            config_id,
+            weapon_name: "M5A3".to_string(),
+            barrel_name: "Factory".to_string(),
+            ammo_type_name: "Standard".to_string(),
+            attachments: Vec::new(),
+            velocity: 800,
+            rpm_single: None,
+            rpm_burst: None,
+            rpm_auto: Some(600),
+            spread_modifier: Decimal::ONE,
+        }
+    }
+
+    #[test]
+    fn test_enumerate_full_loadouts() {
+        let extended = Attachment {
+            velocity_modifier: Decimal::new(110, 2),
+            ..attachment(2, "Muzzle")
+        };
+        let bipod = Attachment {
+            rpm_modifier: Decimal::new(90, 2),
+            ..attachment(4, "Underbarrel")
+        };
+        let attachments = vec![
+            attachment(1, "Muzzle"),
+            attachment(3, "Optic"),
+            extended,
+            bipod,
+        ];
+        let loadouts: Vec<_> = enumerate(vec![bare(1), bare(2)], attachments)
+            .unwrap()
+            .collect();
+
+        // Muzzle is the only slot with a choice, so loadouts come in pairs per configuration
+        assert_eq!(loadouts.len(), 4);
+        for pair in loadouts.chunks(2) {
+            let (first, second) = (&pair[0], &pair[1]);
+            assert_eq!(first.config_id, second.config_id);
+            let slots: Vec<&str> = first.attachments.iter().map(|a| a.slot.as_str()).collect();
+            assert_eq!(slots, ["Muzzle", "Optic", "Underbarrel"]);
+            assert_eq!(second.attachments[0].attachment_id, 2);
+            assert_eq!((first.velocity, second.velocity), (800, 880));
+            assert_eq!((first.rpm_auto, second.rpm_auto), (Some(540), Some(540)));
+        }
+
+        // Weapons without attachments yield their bare configurations
+        let bare: Vec<_> = enumerate(vec![bare(1)], Vec::new()).unwrap().collect();
+        assert_eq!(bare.len(), 1);
+        assert!(bare[0].attachments.is_empty());
+        assert_eq!(bare[0].velocity, 800);
+    }
+
+    #[test]
+    fn test_scale_saturates() {
+        assert_eq!(scale(800, Decimal::new(110, 2)), 880);
+        assert_eq!(scale(30_000, Decimal::new(200, 2)), i16::MAX);
+        assert_eq!(scale(-30_000, Decimal::new(200, 2)), i16::MIN);
+    }
+
+    #[test]
+    fn test_combination_count_overflow() {
+        let slots: Vec<Vec<Attachment>> = ["Optic", "Underbarrel", "Muzzle"]
+            .iter()
+            .map(|slot| (0..3).map(|id| attachment(id, slot)).collect())
+            .collect();
+        assert_eq!(combination_count(&slots).unwrap(), 27);
+        assert_eq!(combination_count(&[]).unwrap(), 1);
+
+        // 10_000^5 exceeds even a 64-bit usize
+        let slots: Vec<Vec<Attachment>> = (0..5)
+            .map(|slot| vec![attachment(0, &slot.to_string()); 10_000])
+            .collect();
+        assert!(combination_count(&slots).is_err());
+        assert!(enumerate(Vec::new(), slots.concat()).is_err());
+    }
+}
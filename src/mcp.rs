@@ -0,0 +1,351 @@
+//! Model Context Protocol server exposing the stats queries as tools
+//!
+//! A `StatsMcpServer` handles one assistant session. Local assistants talk to it
+//! over stdio; `router` serves many sessions over Streamable HTTP, where each
+//! `initialize` request opens a session identified by the `Mcp-Session-Id`
+//! header and responses are streamed as server-sent events. All sessions share
+//! one `StatsClient` and therefore one connection pool.
+//!
+//! Table tools return JSON by default and accept `format` to get the same rows
+//! as a Markdown or HTML table from `render`; `weapon_details` renders a stat card.
+//!
+//! A tool call stops its query as soon as the client cancels the request, the
+//! session ends or the server shuts down, releasing the pooled connection.
+
+use crate::models::WeaponDetails;
+use crate::render::{self, Columns, Render, StatCard, Table, MAX_DECIMAL_PLACES};
+use crate::{StatsClient, StatsError};
+use futures::TryStreamExt;
+use rmcp::handler::server::tool::ToolRouter;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::{
+    CallToolResult, Content, ErrorCode, ErrorData, Implementation, ServerCapabilities, ServerInfo,
+};
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+use rmcp::transport::streamable_http_server::{StreamableHttpServerConfig, StreamableHttpService};
+use rmcp::{tool, tool_handler, tool_router, ServerHandler};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::error;
+
+/// Number of configurations returned by `best_configs_in_category` without a limit
+const DEFAULT_BEST_LIMIT: i64 = 10;
+
+/// Result of a tool call
+type ToolResult = Result<CallToolResult, ErrorData>;
+
+/// Output format of a tool result
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Json,
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct RenderParams {
+    /// Output format: "json" (default), "markdown" or "html"
+    #[serde(default)]
+    pub format: Format,
+    /// Columns of Markdown and HTML tables in display order, tool defaults if omitted
+    pub columns: Option<Vec<String>>,
+    /// Decimal places of Markdown and HTML output, at most 28, decimals as stored if omitted
+    pub decimal_places: Option<u32>,
+}
+
+impl RenderParams {
+    /// Reject decimal places that no decimal can have
+    fn validate(&self) -> Result<(), ErrorData> {
+        match self.decimal_places {
+            Some(places) if places > MAX_DECIMAL_PLACES => Err(ErrorData::invalid_params(
+                format!(
+                    "Decimal places must be at most {}, got {}",
+                    MAX_DECIMAL_PLACES, places
+                ),
+                None,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn render(&self, item: &impl Render) -> Content {
+        Content::text(match self.format {
+            Format::Html => render::html(item),
+            _ => render::markdown(item),
+        })
+    }
+
+    /// Rows as JSON or as a table of the selected columns
+    fn table<T: Columns + Serialize>(&self, rows: &[T]) -> crate::Result<Content> {
+        if self.format == Format::Json {
+            return json(&rows);
+        }
+        let mut table = Table::new(rows);
+        if let Some(columns) = &self.columns {
+            table = table.with_columns(columns)?;
+        }
+        if let Some(places) = self.decimal_places {
+            table = table.with_decimal_places(places);
+        }
+        Ok(self.render(&table))
+    }
+
+    /// Weapon details as JSON or as a stat card
+    fn card(&self, details: &WeaponDetails) -> crate::Result<Content> {
+        if self.format == Format::Json {
+            return json(details);
+        }
+        let mut card = StatCard::from_details(details);
+        if let Some(places) = self.decimal_places {
+            card = card.with_decimal_places(places);
+        }
+        Ok(self.render(&card))
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CategoryParams {
+    /// Category name, e.g. "Assault Rifles"
+    pub category: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct WeaponParams {
+    /// Weapon name, e.g. "M5A3"
+    pub weapon: String,
+    #[serde(flatten)]
+    pub render: RenderParams,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DamageAtRangeParams {
+    /// Weapon name, e.g. "M5A3"
+    pub weapon: String,
+    /// Range in meters
+    pub range: i16,
+    #[serde(flatten)]
+    pub render: RenderParams,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BestConfigsParams {
+    /// Category name, e.g. "Assault Rifles"
+    pub category: String,
+    /// Range in meters, 0 by default
+    #[serde(default)]
+    pub range: i16,
+    /// Maximum number of configurations, 10 by default
+    pub limit: Option<i64>,
+    #[serde(flatten)]
+    pub render: RenderParams,
+}
+
+/// Value serialized as JSON text
+fn json(value: &impl Serialize) -> crate::Result<Content> {
+    Ok(Content::text(serde_json::to_string(value)?))
+}
+
+/// Run a query until it completes or the request is cancelled
+///
+/// Cancellation drops the query future, which aborts its database query.
+async fn run(
+    ct: CancellationToken,
+    query: impl Future<Output = crate::Result<Content>>,
+) -> ToolResult {
+    tokio::select! {
+        result = query => match result {
+            Ok(content) => Ok(CallToolResult::success(vec![content])),
+            Err(err @ (StatsError::NotFound(_) | StatsError::UnknownColumn { .. })) => {
+                Ok(CallToolResult::error(vec![Content::text(err.to_string())]))
+            }
+            Err(err) => {
+                error!("MCP tool call failed: {}", err);
+                Err(ErrorData::internal_error(err.to_string(), None))
+            }
+        },
+        () = ct.cancelled() => Err(ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            "Request cancelled",
+            None,
+        )),
+    }
+}
+
+/// MCP session handler backed by a shared `StatsClient`
+#[derive(Clone)]
+pub struct StatsMcpServer {
+    client: Arc<StatsClient>,
+    tool_router: ToolRouter<Self>,
+}
+
+#[tool_router]
+impl StatsMcpServer {
+    pub fn new(client: Arc<StatsClient>) -> Self {
+        Self {
+            client,
+            tool_router: Self::tool_router(),
+        }
+    }
+
+    #[tool(description = "List weapon categories with their weapon counts")]
+    async fn list_categories(&self, ct: CancellationToken) -> ToolResult {
+        run(ct, async {
+            json(&self.client.categories().try_collect::<Vec<_>>().await?)
+        })
+        .await
+    }
+
+    #[tool(description = "List the weapons of a category")]
+    async fn list_weapons(
+        &self,
+        Parameters(params): Parameters<CategoryParams>,
+        ct: CancellationToken,
+    ) -> ToolResult {
+        run(ct, async {
+            self.client.category(&params.category).await?;
+            let weapons: Vec<_> = self
+                .client
+                .weapons_by_category(&params.category)
+                .try_collect()
+                .await?;
+            json(&weapons)
+        })
+        .await
+    }
+
+    #[tool(
+        description = "Weapon summary with configuration counts, ranges and derived metrics, \
+                       as JSON or a Markdown or HTML stat card"
+    )]
+    async fn weapon_details(
+        &self,
+        Parameters(params): Parameters<WeaponParams>,
+        ct: CancellationToken,
+    ) -> ToolResult {
+        params.render.validate()?;
+        run(ct, async {
+            let details = self
+                .client
+                .weapon_details_aggregated(&params.weapon)
+                .await?;
+            params.render.card(&details)
+        })
+        .await
+    }
+
+    #[tool(
+        description = "All configurations of a weapon with their damage dropoffs and ammo stats"
+    )]
+    async fn weapon_configs(
+        &self,
+        Parameters(params): Parameters<WeaponParams>,
+        ct: CancellationToken,
+    ) -> ToolResult {
+        params.render.validate()?;
+        run(ct, async {
+            self.client.weapon(&params.weapon).await?;
+            let configs: Vec<_> = self
+                .client
+                .weapon_configs_grouped(&params.weapon)
+                .try_collect()
+                .await?;
+            params.render.table(&configs)
+        })
+        .await
+    }
+
+    #[tool(description = "Damage of every configuration of a weapon at a range, highest first")]
+    async fn damage_at_range(
+        &self,
+        Parameters(params): Parameters<DamageAtRangeParams>,
+        ct: CancellationToken,
+    ) -> ToolResult {
+        params.render.validate()?;
+        run(ct, async {
+            self.client.weapon(&params.weapon).await?;
+            let rows: Vec<_> = self
+                .client
+                .damage_at_range(&params.weapon, params.range)
+                .try_collect()
+                .await?;
+            params.render.table(&rows)
+        })
+        .await
+    }
+
+    #[tool(description = "Configurations of a category with the highest damage at a range")]
+    async fn best_configs_in_category(
+        &self,
+        Parameters(params): Parameters<BestConfigsParams>,
+        ct: CancellationToken,
+    ) -> ToolResult {
+        params.render.validate()?;
+        let limit = params.limit.unwrap_or(DEFAULT_BEST_LIMIT);
+        if limit < 0 {
+            return Err(ErrorData::invalid_params(
+                format!("Limit must not be negative, got {}", limit),
+                None,
+            ));
+        }
+        run(ct, async {
+            self.client.category(&params.category).await?;
+            let rows: Vec<_> = self
+                .client
+                .best_configs_in_category(&params.category, params.range, limit)
+                .try_collect()
+                .await?;
+            params.render.table(&rows)
+        })
+        .await
+    }
+}
+
+#[tool_handler]
+impl ServerHandler for StatsMcpServer {
+    fn get_info(&self) -> ServerInfo {
+        ServerInfo {
+            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            server_info: Implementation {
+                name: env!("CARGO_PKG_NAME").to_string(),
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                ..Implementation::default()
+            },
+            instructions: Some(
+                "Battlefield 2042 weapon statistics. Damage values are decimal strings; \
+                 ranges are in meters."
+                    .to_string(),
+            ),
+            ..ServerInfo::default()
+        }
+    }
+}
+
+/// Streamable HTTP service, one `StatsMcpServer` per session
+///
+/// Cancelling `shutdown` terminates every session and its in-flight tool calls.
+pub fn streamable_http_service(
+    client: Arc<StatsClient>,
+    shutdown: CancellationToken,
+) -> StreamableHttpService<StatsMcpServer> {
+    StreamableHttpService::new(
+        move || Ok(StatsMcpServer::new(client.clone())),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig {
+            cancellation_token: shutdown,
+            ..StreamableHttpServerConfig::default()
+        },
+    )
+}
+
+/// Router serving the MCP endpoint at `/mcp`
+pub fn router<S>(client: Arc<StatsClient>, shutdown: CancellationToken) -> axum::Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    axum::Router::new().nest_service("/mcp", streamable_http_service(client, shutdown))
+}
@@ -0,0 +1,36 @@
+//! Attachment and loadout data structures
+
+use crate::models::weapon::Modifier;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Attachment with its stat modifiers (attachments.csv)
+///
+/// Modifiers scale the configuration stat they apply to; `1.00` leaves it unchanged.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Attachment {
+    pub attachment_id: i32,
+    /// Attachment slot, e.g. "Optic", "Underbarrel" or "Muzzle"
+    pub slot: String,
+    pub attachment_name: String,
+    pub velocity_modifier: Modifier,
+    pub rpm_modifier: Modifier,
+    pub spread_modifier: Modifier,
+}
+
+/// Configuration with one attachment per available slot and derived stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Loadout {
+    pub config_id: i32,
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    /// Attachments ordered by slot
+    pub attachments: Vec<Attachment>,
+    pub velocity: i16,
+    pub rpm_single: Option<i16>,
+    pub rpm_burst: Option<i16>,
+    pub rpm_auto: Option<i16>,
+    /// Combined spread modifier of all attachments
+    pub spread_modifier: Modifier,
+}
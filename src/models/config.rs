@@ -1,5 +1,8 @@
 //! Configuration-related data structures
 
+use crate::{Result, StatsError};
+use std::env;
+
 /// Database configuration
 #[derive(Debug, Clone)]
 pub struct DatabaseConfig {
@@ -22,8 +25,58 @@ impl DatabaseConfig {
         self
     }
 
+    /// Read the configuration from the environment
+    ///
+    /// `DATABASE_URL` is required; `DATABASE_MAX_CONNECTIONS` optionally
+    /// overrides the pool size.
+    pub fn from_env() -> Result<Self> {
+        let url = env::var("DATABASE_URL")
+            .map_err(|_| StatsError::ConfigError("DATABASE_URL must be set".to_string()))?;
+        let mut config = Self::new(url);
+        if let Ok(max_connections) = env::var("DATABASE_MAX_CONNECTIONS") {
+            let max_connections = max_connections.parse().map_err(|_| {
+                StatsError::ConfigError(format!(
+                    "DATABASE_MAX_CONNECTIONS must be a number, got '{}'",
+                    max_connections
+                ))
+            })?;
+            config = config.with_max_connections(max_connections);
+        }
+        Ok(config)
+    }
+
     /// Get the database URL
     pub fn url(&self) -> &str {
         &self.url
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_env() {
+        env::remove_var("DATABASE_URL");
+        assert!(matches!(
+            DatabaseConfig::from_env(),
+            Err(StatsError::ConfigError(_))
+        ));
+
+        env::set_var("DATABASE_URL", "postgres://localhost/stats");
+        env::set_var("DATABASE_MAX_CONNECTIONS", "4");
+        let config = DatabaseConfig::from_env().unwrap();
+        assert_eq!(config.url(), "postgres://localhost/stats");
+        assert_eq!(config.max_connections, 4);
+
+        env::set_var("DATABASE_MAX_CONNECTIONS", "many");
+        assert!(matches!(
+            DatabaseConfig::from_env(),
+            Err(StatsError::ConfigError(_))
+        ));
+
+        env::remove_var("DATABASE_MAX_CONNECTIONS");
+        assert_eq!(DatabaseConfig::from_env().unwrap().max_connections, 10);
+        env::remove_var("DATABASE_URL");
+    }
+}
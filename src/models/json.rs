@@ -1,13 +1,15 @@
 //! JSON data structures for parsing weapons.json
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use crate::models::weapon::{Damage, ReloadTime, HeadshotMultiplier};
+use crate::models::weapon::{Angle, Damage, ReloadTime, HeadshotMultiplier, Modifier};
 
 /// Root structure of weapons.json
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeaponsData {
     pub categories: Vec<CategoryData>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<AttachmentData>,
 }
 
 /// Category with weapons
@@ -22,8 +24,9 @@ pub struct CategoryData {
 pub struct WeaponData {
     pub name: String,
     pub stats: Vec<WeaponStatData>,
+    /// Keyed by ammo type name, in document order
     #[serde(rename = "ammoStats")]
-    pub ammo_stats: HashMap<String, AmmoStatData>,
+    pub ammo_stats: IndexMap<String, AmmoStatData>,
 }
 
 /// Individual weapon configuration (barrel + ammo combination)
@@ -33,19 +36,62 @@ pub struct WeaponStatData {
     pub barrel_type: String,
     pub dropoffs: Vec<DamageDropoffData>,
     pub velocity: i16,
-    #[serde(rename = "rpmSingle")]
+    #[serde(rename = "rpmSingle", default, skip_serializing_if = "Option::is_none")]
     pub rpm_single: Option<i16>,
-    #[serde(rename = "rpmBurst")]
+    #[serde(rename = "rpmBurst", default, skip_serializing_if = "Option::is_none")]
     pub rpm_burst: Option<i16>,
-    #[serde(rename = "rpmAuto")]
+    #[serde(rename = "rpmAuto", default, skip_serializing_if = "Option::is_none")]
     pub rpm_auto: Option<i16>,
     #[serde(rename = "ammoType")]
     pub ammo_type: String,
+    #[serde(
+        rename = "recoilVertical",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub recoil_vertical: Option<Angle>,
+    #[serde(
+        rename = "recoilHorizontal",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub recoil_horizontal: Option<Angle>,
+    #[serde(
+        rename = "recoilFirstShotMultiplier",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub recoil_first_shot_multiplier: Option<Modifier>,
+    #[serde(
+        rename = "spreadAds",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub spread_ads: Option<Angle>,
+    #[serde(
+        rename = "spreadHipfire",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub spread_hipfire: Option<Angle>,
+    #[serde(
+        rename = "spreadBloomPerShot",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub spread_bloom_per_shot: Option<Angle>,
 }
 
 /// Damage dropoff at specific range
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DamageDropoffData {
+    #[serde(with = "number")]
     pub damage: Damage,
     pub range: i16,
 }
@@ -55,12 +101,149 @@ pub struct DamageDropoffData {
 pub struct AmmoStatData {
     #[serde(rename = "magSize")]
     pub mag_size: i16,
-    #[serde(rename = "headshotMultiplier")]
+    #[serde(rename = "headshotMultiplier", with = "number")]
     pub headshot_multiplier: HeadshotMultiplier,
-    #[serde(rename = "emptyReload")]
+    #[serde(
+        rename = "emptyReload",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
     pub empty_reload: Option<ReloadTime>,
-    #[serde(rename = "tacticalReload")]
+    #[serde(
+        rename = "tacticalReload",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
     pub tactical_reload: Option<ReloadTime>,
-    #[serde(rename = "pelletCount")]
+    #[serde(rename = "pelletCount", default, skip_serializing_if = "Option::is_none")]
     pub pellet_count: Option<i16>,
+    #[serde(
+        rename = "upperChestMultiplier",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub upper_chest_multiplier: Option<Modifier>,
+    #[serde(
+        rename = "limbMultiplier",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub limb_multiplier: Option<Modifier>,
+}
+
+/// Attachment with the weapons it fits on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentData {
+    pub name: String,
+    pub slot: String,
+    /// Names of compatible weapons
+    pub weapons: Vec<String>,
+    #[serde(
+        rename = "velocityModifier",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub velocity_modifier: Option<Modifier>,
+    #[serde(
+        rename = "rpmModifier",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub rpm_modifier: Option<Modifier>,
+    #[serde(
+        rename = "spreadModifier",
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "number::option"
+    )]
+    pub spread_modifier: Option<Modifier>,
+}
+
+/// Decimals written as JSON numbers, as weapons.json stores them
+///
+/// Whole values are written as integers and the rest as the shortest float
+/// that reads back to the same value, so `24` stays `24` and `21.6` stays `21.6`.
+/// Floats are read through that same shortest form, which keeps values such as
+/// `22.080000000000002` intact, and decimal strings are accepted as well.
+mod number {
+    use rust_decimal::prelude::ToPrimitive;
+    use rust_decimal::Decimal;
+    use serde::de::{self, Deserializer, Visitor};
+    use serde::ser::{Error as _, Serializer};
+    use std::fmt;
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = value.normalize();
+        match value.to_i64() {
+            Some(whole) if value.scale() == 0 => serializer.serialize_i64(whole),
+            // Parsed from the decimal text, as `to_f64` can be off in the last digit
+            _ => {
+                let float = f64::from_str(&value.to_string()).map_err(S::Error::custom)?;
+                serializer.serialize_f64(float)
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+        deserializer.deserialize_any(DecimalVisitor)
+    }
+
+    struct DecimalVisitor;
+
+    impl Visitor<'_> for DecimalVisitor {
+        type Value = Decimal;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a decimal number")
+        }
+
+        fn visit_i64<E: de::Error>(self, value: i64) -> Result<Decimal, E> {
+            Ok(Decimal::from(value))
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Decimal, E> {
+            Ok(Decimal::from(value))
+        }
+
+        fn visit_f64<E: de::Error>(self, value: f64) -> Result<Decimal, E> {
+            self.visit_str(&value.to_string())
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Decimal, E> {
+            Decimal::from_str(value)
+                .or_else(|_| Decimal::from_scientific(value))
+                .map_err(E::custom)
+        }
+    }
+
+    pub mod option {
+        use rust_decimal::Decimal;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(
+            value: &Option<Decimal>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match value {
+                Some(value) => super::serialize(value, serializer),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Decimal>, D::Error> {
+            #[derive(Deserialize)]
+            struct Number(#[serde(with = "super")] Decimal);
+
+            Ok(Option::<Number>::deserialize(deserializer)?.map(|Number(value)| value))
+        }
+    }
 }
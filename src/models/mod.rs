@@ -3,7 +3,15 @@
 pub mod weapon;
 pub mod config;
 pub mod json;
+pub mod target;
+pub mod attachment;
+pub mod query;
+pub mod page;
 
 pub use weapon::*;
 pub use config::*;
 pub use json::*;
+pub use target::*;
+pub use attachment::*;
+pub use query::*;
+pub use page::*;
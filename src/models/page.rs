@@ -0,0 +1,75 @@
+//! Keyset pagination
+
+use crate::{Result, StatsError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Opaque position in an ordered query, encoding the ORDER BY keys of the last item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// Encode the sort keys of an item
+    pub fn encode<K: Serialize>(key: &K) -> Self {
+        let json = serde_json::to_vec(key).unwrap_or_default();
+        Self(json.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+
+    /// Decode the sort keys this cursor was created from
+    pub fn decode<K: DeserializeOwned>(&self) -> Result<K> {
+        let invalid = || StatsError::QueryFailed(format!("Invalid cursor '{}'", self.0));
+        let bytes = (0..self.0.len())
+            .step_by(2)
+            .map(|idx| {
+                self.0
+                    .get(idx..idx + 2)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            })
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+
+    /// Cursor string as handed to clients
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(cursor: String) -> Self {
+        Self(cursor)
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// One page of an ordered query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Cursor of the next page, `None` on the last page
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// Build a page from up to `limit + 1` fetched items
+    ///
+    /// The extra item only signals that another page exists and is dropped.
+    pub fn from_items<K: Serialize>(mut items: Vec<T>, limit: i64, key: impl Fn(&T) -> K) -> Self {
+        let limit = usize::try_from(limit).unwrap_or(0);
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(|item| Cursor::encode(&key(item)))
+        } else {
+            None
+        };
+        Self { items, next_cursor }
+    }
+}
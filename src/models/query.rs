@@ -0,0 +1,226 @@
+//! Composable configuration filters
+
+use crate::models::target::TargetProfile;
+use crate::models::weapon::{BestConfigInCategory, Damage, RankBy, ReloadTime};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Sort key of a configuration query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSort {
+    /// Damage at the query range
+    #[default]
+    Damage,
+    Velocity,
+    /// Sustained fire rate: automatic, else single fire
+    FireRate,
+    MagazineSize,
+    /// Tactical reload time, falling back to empty reload
+    ReloadTime,
+}
+
+impl ConfigSort {
+    /// Key name understood by the configuration query SQL
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigSort::Damage => "damage",
+            ConfigSort::Velocity => "velocity",
+            ConfigSort::FireRate => "fire_rate",
+            ConfigSort::MagazineSize => "magazine_size",
+            ConfigSort::ReloadTime => "reload_time",
+        }
+    }
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Filters, sort and window for `StatsClient::query_configs`
+///
+/// Every filter is optional; damage is evaluated at `range` (0 by default)
+/// using the same step function as the range queries.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigQuery {
+    pub category: Option<String>,
+    pub weapon: Option<String>,
+    pub barrel: Option<String>,
+    pub ammo: Option<String>,
+    pub min_velocity: Option<i16>,
+    pub max_velocity: Option<i16>,
+    /// Bounds on the sustained fire rate
+    pub min_rpm: Option<i16>,
+    pub max_rpm: Option<i16>,
+    pub min_magazine: Option<i16>,
+    pub max_magazine: Option<i16>,
+    /// Upper bound on tactical reload time, falling back to empty reload
+    pub max_reload: Option<ReloadTime>,
+    pub range: i16,
+    pub min_damage: Option<Damage>,
+    pub max_damage: Option<Damage>,
+    pub sort: ConfigSort,
+    pub order: SortOrder,
+    pub limit: Option<i64>,
+    pub offset: i64,
+}
+
+impl ConfigQuery {
+    /// Create an unfiltered query sorted by damage, highest first
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only weapons in a category
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Only configurations of a weapon
+    pub fn with_weapon(mut self, weapon: impl Into<String>) -> Self {
+        self.weapon = Some(weapon.into());
+        self
+    }
+
+    /// Only configurations with a barrel
+    pub fn with_barrel(mut self, barrel: impl Into<String>) -> Self {
+        self.barrel = Some(barrel.into());
+        self
+    }
+
+    /// Only configurations with an ammo type
+    pub fn with_ammo(mut self, ammo: impl Into<String>) -> Self {
+        self.ammo = Some(ammo.into());
+        self
+    }
+
+    /// Inclusive velocity bounds
+    pub fn with_velocity(mut self, min: Option<i16>, max: Option<i16>) -> Self {
+        self.min_velocity = min;
+        self.max_velocity = max;
+        self
+    }
+
+    /// Inclusive bounds on the sustained fire rate (automatic, else single fire)
+    pub fn with_rpm(mut self, min: Option<i16>, max: Option<i16>) -> Self {
+        self.min_rpm = min;
+        self.max_rpm = max;
+        self
+    }
+
+    /// Inclusive magazine size bounds
+    pub fn with_magazine(mut self, min: Option<i16>, max: Option<i16>) -> Self {
+        self.min_magazine = min;
+        self.max_magazine = max;
+        self
+    }
+
+    /// Slowest acceptable reload
+    pub fn with_max_reload(mut self, max_reload: ReloadTime) -> Self {
+        self.max_reload = Some(max_reload);
+        self
+    }
+
+    /// Range at which damage is evaluated
+    pub fn at_range(mut self, range: i16) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Inclusive damage bounds at the query range
+    pub fn with_damage(mut self, min: Option<Damage>, max: Option<Damage>) -> Self {
+        self.min_damage = min;
+        self.max_damage = max;
+        self
+    }
+
+    /// Set the sort key and direction
+    pub fn sort_by(mut self, sort: ConfigSort, order: SortOrder) -> Self {
+        self.sort = sort;
+        self.order = order;
+        self
+    }
+
+    /// Return at most `limit` results
+    pub fn with_limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip the first `offset` results
+    pub fn with_offset(mut self, offset: i64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Signed sort value of a result, matching the SQL ordering (ascending, nulls last)
+    pub fn sort_value(&self, row: &BestConfigInCategory) -> Option<Decimal> {
+        let value = match self.sort {
+            ConfigSort::Damage => Some(row.damage),
+            ConfigSort::Velocity => Some(Decimal::from(row.velocity)),
+            ConfigSort::FireRate => {
+                crate::ballistics::fire_rate(row.rpm_single, row.rpm_auto).map(Decimal::from)
+            }
+            ConfigSort::MagazineSize => Some(Decimal::from(row.magazine_size)),
+            ConfigSort::ReloadTime => row.tactical_reload_time.or(row.empty_reload_time),
+        }?;
+        Some(match self.order {
+            SortOrder::Asc => value,
+            SortOrder::Desc => -value,
+        })
+    }
+}
+
+/// Bands, ranking and target for `StatsClient::range_band_winners`
+///
+/// Bands are `band_width` meters wide starting at 0, with a final open-ended
+/// band from `open_band_start`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeBandQuery {
+    pub band_width: i16,
+    pub open_band_start: i16,
+    pub rank_by: RankBy,
+    /// Winners kept per band
+    pub limit: i64,
+    pub target: TargetProfile,
+}
+
+impl RangeBandQuery {
+    /// Create a query for the single fastest-killing configuration of each
+    /// band against the default target
+    pub fn new(band_width: i16, open_band_start: i16) -> Self {
+        Self {
+            band_width,
+            open_band_start,
+            rank_by: RankBy::Ttk,
+            limit: 1,
+            target: TargetProfile::default(),
+        }
+    }
+
+    /// Set the ranking criterion
+    pub fn rank_by(mut self, rank_by: RankBy) -> Self {
+        self.rank_by = rank_by;
+        self
+    }
+
+    /// Keep the top `limit` configurations of each band
+    pub fn with_limit(mut self, limit: i64) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Rank against a target profile
+    pub fn against(mut self, target: TargetProfile) -> Self {
+        self.target = target;
+        self
+    }
+}
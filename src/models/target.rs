@@ -0,0 +1,104 @@
+//! Target profiles used by kill calculations
+
+use crate::ballistics::DEFAULT_TARGET_HEALTH;
+use crate::models::weapon::{HeadshotMultiplier, HitboxMultipliers, Modifier};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Body part hit by a projectile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Hitbox {
+    Head,
+    UpperChest,
+    Torso,
+    Limb,
+}
+
+/// Target health, armor and body-part multipliers (target_profiles.csv)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct TargetProfile {
+    pub profile_name: String,
+    pub base_health: i16,
+    /// Armor vest points absorbed before health
+    pub armor_health: i16,
+    /// Fraction of damage the vest absorbs without losing armor points
+    pub armor_damage_reduction: Modifier,
+    pub limb_multiplier: Modifier,
+    /// Replaces the ammo headshot multiplier, e.g. for helmets
+    pub headshot_multiplier_override: Option<HeadshotMultiplier>,
+}
+
+impl TargetProfile {
+    /// Unarmored soldier
+    pub fn soldier() -> Self {
+        Self {
+            profile_name: "Soldier".to_string(),
+            base_health: DEFAULT_TARGET_HEALTH,
+            armor_health: 0,
+            armor_damage_reduction: Decimal::ZERO,
+            limb_multiplier: Decimal::ONE,
+            headshot_multiplier_override: None,
+        }
+    }
+
+    /// Soldier wearing an armor vest
+    pub fn armored() -> Self {
+        Self {
+            profile_name: "Armored".to_string(),
+            armor_health: 50,
+            ..Self::soldier()
+        }
+    }
+
+    /// Built-in profiles stored on population
+    pub fn presets() -> Vec<Self> {
+        vec![Self::soldier(), Self::armored()]
+    }
+
+    /// Check that the armor can be stripped
+    ///
+    /// Armor absorbing every hit would never break, so the reduction must be
+    /// in [0, 1), matching the database constraint.
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if !(Decimal::ZERO..Decimal::ONE).contains(&self.armor_damage_reduction) {
+            return Err(format!(
+                "Armor damage reduction must be in [0, 1), got {}",
+                self.armor_damage_reduction
+            ));
+        }
+        Ok(())
+    }
+
+    /// Raw damage needed to kill the target
+    ///
+    /// Armor absorbing a fraction `r` of each hit costs `armor / (1 - r)`
+    /// raw damage to strip, after which hits go straight to health.
+    ///
+    /// Only meaningful for profiles that pass `validate`; a reduction of
+    /// exactly 1 panics on the division.
+    pub fn effective_health(&self) -> Decimal {
+        let armor = Decimal::from(self.armor_health);
+        let armor_cost = armor / (Decimal::ONE - self.armor_damage_reduction);
+        Decimal::from(self.base_health) + armor_cost
+    }
+
+    /// Damage multiplier for a hit on `hitbox` with the given ammo multipliers
+    ///
+    /// The target's headshot override replaces the ammo headshot multiplier,
+    /// and its limb multiplier stacks with the ammo limb multiplier.
+    pub fn hit_multiplier(&self, hitbox: Hitbox, ammo: &HitboxMultipliers) -> Decimal {
+        match hitbox {
+            Hitbox::Head => self.headshot_multiplier_override.unwrap_or(ammo.headshot),
+            Hitbox::Limb => ammo.multiplier(Hitbox::Limb) * self.limb_multiplier,
+            _ => ammo.multiplier(hitbox),
+        }
+    }
+}
+
+impl Default for TargetProfile {
+    fn default() -> Self {
+        Self::soldier()
+    }
+}
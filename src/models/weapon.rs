@@ -1,6 +1,7 @@
 //! Weapon-related data structures
 
 use serde::{Deserialize, Serialize};
+use crate::models::target::{Hitbox, TargetProfile};
 use sqlx::FromRow;
 
 /// Custom decimal type for precise damage calculations
@@ -15,6 +16,14 @@ pub type ReloadTime = rust_decimal::Decimal;
 /// Represents DECIMAL(3,1) - 2 digits before decimal, 1 after (e.g., 99.9)
 pub type HeadshotMultiplier = rust_decimal::Decimal;
 
+/// Custom decimal type for fractional modifiers
+/// Represents DECIMAL(3,2) - 1 digit before decimal, 2 after (e.g., 9.99)
+pub type Modifier = rust_decimal::Decimal;
+
+/// Custom decimal type for recoil and spread angles in degrees
+/// Represents DECIMAL(5,3) - 2 digits before decimal, 3 after (e.g., 99.999)
+pub type Angle = rust_decimal::Decimal;
+
 /// Weapon category (categories.csv)
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Category {
@@ -24,6 +33,7 @@ pub struct Category {
 
 /// Basic weapon information (weapons.csv)
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
 pub struct Weapon {
     pub weapon_id: i32,
     pub weapon_name: String,
@@ -44,6 +54,41 @@ pub struct AmmoType {
     pub ammo_type_name: String,
 }
 
+/// Category with the number of weapons in it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
+pub struct CategoryWithCount {
+    pub category_id: i32,
+    pub category_name: String,
+    pub weapon_count: i64,
+}
+
+/// Barrel with the number of configurations using it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BarrelWithCount {
+    pub barrel_id: i32,
+    pub barrel_name: String,
+    pub config_count: i64,
+}
+
+/// Ammo type with the number of configurations using it
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AmmoTypeWithCount {
+    pub ammo_id: i32,
+    pub ammo_type_name: String,
+    pub config_count: i64,
+}
+
+/// Weapon with its category name and number of configurations
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WeaponWithCategory {
+    pub weapon_id: i32,
+    pub weapon_name: String,
+    pub category_id: i32,
+    pub category_name: String,
+    pub config_count: i64,
+}
+
 /// Weapon ammo-specific stats (weapon_ammo_stats.csv)
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct WeaponAmmoStats {
@@ -54,6 +99,8 @@ pub struct WeaponAmmoStats {
     pub tactical_reload_time: Option<ReloadTime>,
     pub headshot_multiplier: HeadshotMultiplier,
     pub pellet_count: Option<i16>,
+    pub upper_chest_multiplier: Option<Modifier>,
+    pub limb_multiplier: Option<Modifier>,
 }
 
 /// Weapon configuration with barrel and ammo (configurations.csv)
@@ -67,6 +114,12 @@ pub struct Configuration {
     pub rpm_single: Option<i16>,
     pub rpm_burst: Option<i16>,
     pub rpm_auto: Option<i16>,
+    pub recoil_vertical: Option<Angle>,
+    pub recoil_horizontal: Option<Angle>,
+    pub recoil_first_shot_multiplier: Option<Modifier>,
+    pub spread_ads: Option<Angle>,
+    pub spread_hipfire: Option<Angle>,
+    pub spread_bloom_per_shot: Option<Angle>,
 }
 
 /// Damage dropoff at specific ranges (config_dropoffs.csv)
@@ -79,6 +132,7 @@ pub struct ConfigDropoff {
 
 /// Combined weapon configuration with dropoffs for streaming
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
 pub struct WeaponConfigWithDropoffs {
     pub config_id: i32,
     pub weapon_name: String,
@@ -88,43 +142,266 @@ pub struct WeaponConfigWithDropoffs {
     pub rpm_single: Option<i16>,
     pub rpm_burst: Option<i16>,
     pub rpm_auto: Option<i16>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub recoil_vertical: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub recoil_horizontal: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub recoil_first_shot_multiplier: Option<Modifier>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_ads: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_hipfire: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_bloom_per_shot: Option<Angle>,
+    pub range: i16,
+    #[cfg_attr(feature = "http", schema(value_type = crate::openapi::Damage))]
+    pub damage: Damage,
+}
+
+/// Damage at the start of a dropoff range
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
+pub struct DamageDropoff {
     pub range: i16,
+    #[cfg_attr(feature = "http", schema(value_type = crate::openapi::Damage))]
     pub damage: Damage,
 }
 
+/// Weapon configuration with all of its dropoffs and ammo stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
+pub struct WeaponConfig {
+    pub config_id: i32,
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    pub velocity: i16,
+    pub rpm_single: Option<i16>,
+    pub rpm_burst: Option<i16>,
+    pub rpm_auto: Option<i16>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub recoil_vertical: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub recoil_horizontal: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub recoil_first_shot_multiplier: Option<Modifier>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_ads: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_hipfire: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_bloom_per_shot: Option<Angle>,
+    /// Dropoffs ordered by range
+    pub dropoffs: Vec<DamageDropoff>,
+    /// Stats of the configuration's ammo type, if recorded for the weapon
+    pub ammo_stats: Option<WeaponAmmoStatsWithNames>,
+}
+
+/// Weapon with a subset of its configurations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeaponWithConfigs {
+    pub weapon_name: String,
+    pub configurations: Vec<WeaponConfig>,
+}
+
+impl WeaponConfig {
+    /// Sustained fire rate, see `ballistics::fire_rate`
+    pub fn fire_rate(&self) -> Option<i16> {
+        crate::ballistics::fire_rate(self.rpm_single, self.rpm_auto)
+    }
+
+    /// Damage at `range` from the dropoff table
+    pub fn damage_at(&self, range: i16) -> Option<Damage> {
+        crate::ballistics::damage_at(
+            self.dropoffs.iter().map(|dropoff| (dropoff.range, dropoff.damage)),
+            range,
+        )
+    }
+
+    /// Pellets per shot of the configuration's ammo, if recorded
+    pub fn pellet_count(&self) -> Option<i16> {
+        self.ammo_stats.as_ref().and_then(|stats| stats.pellet_count)
+    }
+
+    /// Shots to deal `health` damage at `range`, counting every pellet of a shot
+    pub fn bullets_to_kill(&self, range: i16, health: rust_decimal::Decimal) -> Option<u32> {
+        crate::ballistics::bullets_to_kill(self.damage_at(range)?, self.pellet_count(), health)
+    }
+}
+
+impl From<WeaponConfigWithDropoffs> for WeaponConfig {
+    /// Start a configuration from its first dropoff row
+    fn from(row: WeaponConfigWithDropoffs) -> Self {
+        Self {
+            config_id: row.config_id,
+            weapon_name: row.weapon_name,
+            barrel_name: row.barrel_name,
+            ammo_type_name: row.ammo_type_name,
+            velocity: row.velocity,
+            rpm_single: row.rpm_single,
+            rpm_burst: row.rpm_burst,
+            rpm_auto: row.rpm_auto,
+            recoil_vertical: row.recoil_vertical,
+            recoil_horizontal: row.recoil_horizontal,
+            recoil_first_shot_multiplier: row.recoil_first_shot_multiplier,
+            spread_ads: row.spread_ads,
+            spread_hipfire: row.spread_hipfire,
+            spread_bloom_per_shot: row.spread_bloom_per_shot,
+            dropoffs: vec![DamageDropoff {
+                range: row.range,
+                damage: row.damage,
+            }],
+            ammo_stats: None,
+        }
+    }
+}
+
 /// Weapon ammo stats with names for streaming
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
 pub struct WeaponAmmoStatsWithNames {
     pub weapon_name: String,
     pub ammo_type_name: String,
     pub magazine_size: i16,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::ReloadTime>))]
     pub empty_reload_time: Option<ReloadTime>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::ReloadTime>))]
     pub tactical_reload_time: Option<ReloadTime>,
+    #[cfg_attr(feature = "http", schema(value_type = crate::openapi::HeadshotMultiplier))]
     pub headshot_multiplier: HeadshotMultiplier,
     pub pellet_count: Option<i16>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub upper_chest_multiplier: Option<Modifier>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub limb_multiplier: Option<Modifier>,
+}
+
+impl WeaponAmmoStatsWithNames {
+    /// Per-hitbox multipliers of this ammo type
+    pub fn hitbox_multipliers(&self) -> HitboxMultipliers {
+        HitboxMultipliers {
+            headshot: self.headshot_multiplier,
+            upper_chest: self.upper_chest_multiplier,
+            limb: self.limb_multiplier,
+        }
+    }
+}
+
+/// Per-hitbox damage multipliers of a weapon and ammo type
+///
+/// Missing multipliers deal torso damage.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitboxMultipliers {
+    pub headshot: HeadshotMultiplier,
+    pub upper_chest: Option<Modifier>,
+    pub limb: Option<Modifier>,
+}
+
+impl HitboxMultipliers {
+    /// Damage multiplier for a hit on `hitbox`
+    pub fn multiplier(&self, hitbox: Hitbox) -> Modifier {
+        match hitbox {
+            Hitbox::Head => self.headshot,
+            Hitbox::UpperChest => self.upper_chest.unwrap_or(Modifier::ONE),
+            Hitbox::Torso => Modifier::ONE,
+            Hitbox::Limb => self.limb.unwrap_or(Modifier::ONE),
+        }
+    }
+}
+
+impl Default for HitboxMultipliers {
+    fn default() -> Self {
+        Self {
+            headshot: HeadshotMultiplier::ONE,
+            upper_chest: None,
+            limb: None,
+        }
+    }
 }
 
 /// Complex query result for damage at range
+///
+/// Engagement columns are only filled by `StatsClient::engagement_at_range`.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
 pub struct DamageAtRange {
     pub weapon_name: String,
     pub barrel_name: String,
     pub ammo_type_name: String,
     pub effective_range: i16,
+    #[cfg_attr(feature = "http", schema(value_type = crate::openapi::Damage))]
     pub damage: Damage,
     pub velocity: i16,
     pub rpm_single: Option<i16>,
     pub rpm_burst: Option<i16>,
     pub rpm_auto: Option<i16>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::HeadshotMultiplier>))]
+    pub headshot_multiplier: Option<HeadshotMultiplier>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub upper_chest_multiplier: Option<Modifier>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub limb_multiplier: Option<Modifier>,
+    /// Pellets per shot, damage is per pellet
+    pub pellet_count: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flight_time_ms: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bullet_drop: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lead_distance: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_ttk_ms: Option<f64>,
 }
 
-/// Best configuration in category result
+/// Best configuration in category result, also returned by `ConfigQuery`
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
 pub struct BestConfigInCategory {
+    pub config_id: i32,
+    pub category_name: String,
     pub weapon_name: String,
     pub barrel_name: String,
     pub ammo_type_name: String,
     pub effective_range: i16,
+    #[cfg_attr(feature = "http", schema(value_type = crate::openapi::Damage))]
+    pub damage: Damage,
+    pub velocity: i16,
+    pub rpm_single: Option<i16>,
+    pub rpm_burst: Option<i16>,
+    pub rpm_auto: Option<i16>,
+    pub magazine_size: i16,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::ReloadTime>))]
+    pub empty_reload_time: Option<ReloadTime>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::ReloadTime>))]
+    pub tactical_reload_time: Option<ReloadTime>,
+    #[cfg_attr(feature = "http", schema(value_type = crate::openapi::HeadshotMultiplier))]
+    pub headshot_multiplier: HeadshotMultiplier,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub recoil_vertical: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub recoil_horizontal: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Modifier>))]
+    pub recoil_first_shot_multiplier: Option<Modifier>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_ads: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_hipfire: Option<Angle>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Angle>))]
+    pub spread_bloom_per_shot: Option<Angle>,
+}
+
+/// One dropoff of a configuration with its weapon, ammo and fire rate columns
+///
+/// Like `ConfigQuery`, only configurations whose weapon has stats for their ammo type are listed.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DamageTableRow {
+    pub config_id: i32,
+    pub weapon_name: String,
+    pub category_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    pub range: i16,
     pub damage: Damage,
     pub velocity: i16,
     pub rpm_single: Option<i16>,
@@ -134,14 +411,128 @@ pub struct BestConfigInCategory {
     pub empty_reload_time: Option<ReloadTime>,
     pub tactical_reload_time: Option<ReloadTime>,
     pub headshot_multiplier: HeadshotMultiplier,
+    pub pellet_count: Option<i16>,
+}
+
+impl DamageAtRange {
+    /// Per-hitbox multipliers of this row's ammo type
+    pub fn hitbox_multipliers(&self) -> HitboxMultipliers {
+        HitboxMultipliers {
+            headshot: self.headshot_multiplier.unwrap_or(HeadshotMultiplier::ONE),
+            upper_chest: self.upper_chest_multiplier,
+            limb: self.limb_multiplier,
+        }
+    }
+}
+
+/// Hits to kill per hitbox at a range
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HitboxBulletsToKill {
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    pub effective_range: i16,
+    pub damage: Damage,
+    pub head: Option<u32>,
+    pub upper_chest: Option<u32>,
+    pub torso: Option<u32>,
+    pub limb: Option<u32>,
+}
+
+/// Ranking criterion for range-based queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RankBy {
+    /// Lowest time to kill first, ties broken by damage
+    Ttk,
+    /// Highest damage per shot first, counting every pellet
+    Damage,
+}
+
+/// Top configuration within a range band across all categories
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RangeBandWinner {
+    pub band_start: i16,
+    /// Exclusive upper bound, `None` for the open-ended last band
+    pub band_end: Option<i16>,
+    pub rank: i64,
+    pub category_name: String,
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    pub effective_range: i16,
+    /// Damage per projectile
+    pub damage: Damage,
+    /// Pellets per shot, if recorded for the configuration's ammo
+    pub pellet_count: Option<i16>,
+    /// Shots to kill, counting every pellet of a shot
+    pub bullets_to_kill: Option<i32>,
+    pub ttk_ms: Option<f64>,
+    pub velocity: i16,
+    pub rpm_single: Option<i16>,
+    pub rpm_burst: Option<i16>,
+    pub rpm_auto: Option<i16>,
 }
 
 /// Complete weapon details including all configurations and stats
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
 pub struct WeaponDetails {
     pub weapon: Weapon,
-    pub configurations: Vec<WeaponConfigWithDropoffs>,
+    pub category_name: String,
+    pub configurations: Vec<WeaponConfig>,
     pub ammo_stats: Vec<WeaponAmmoStatsWithNames>,
+    pub metrics: WeaponMetrics,
+}
+
+/// Summary metrics across all configurations of a weapon
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "http", derive(utoipa::ToSchema))]
+pub struct WeaponMetrics {
+    pub configuration_count: usize,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Damage>))]
+    pub max_damage: Option<Damage>,
+    #[cfg_attr(feature = "http", schema(value_type = Option<crate::openapi::Damage>))]
+    pub min_damage: Option<Damage>,
+    pub max_velocity: Option<i16>,
+    pub max_fire_rate: Option<i16>,
+    pub max_magazine_size: Option<i16>,
+    /// Fastest close-range time to kill against the default target
+    pub best_ttk_ms: Option<f64>,
+}
+
+impl WeaponMetrics {
+    /// Derive metrics from a weapon's configurations
+    pub fn from_configs(configs: &[WeaponConfig], target: &TargetProfile) -> Self {
+        let health = target.effective_health();
+        let damages = || {
+            configs
+                .iter()
+                .flat_map(|config| config.dropoffs.iter().map(|dropoff| dropoff.damage))
+        };
+
+        Self {
+            configuration_count: configs.len(),
+            max_damage: damages().max(),
+            min_damage: damages().min(),
+            max_velocity: configs.iter().map(|config| config.velocity).max(),
+            max_fire_rate: configs.iter().filter_map(WeaponConfig::fire_rate).max(),
+            max_magazine_size: configs
+                .iter()
+                .filter_map(|config| config.ammo_stats.as_ref())
+                .map(|stats| stats.magazine_size)
+                .max(),
+            best_ttk_ms: configs
+                .iter()
+                .filter_map(|config| {
+                    let damage = config.dropoffs.first()?.damage;
+                    let btk =
+                        crate::ballistics::bullets_to_kill(damage, config.pellet_count(), health)?;
+                    crate::ballistics::time_to_kill_ms(btk, config.fire_rate()?)
+                })
+                .min_by(f64::total_cmp),
+        }
+    }
 }
 
 /// Database validation report
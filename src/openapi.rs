@@ -0,0 +1,78 @@
+//! OpenAPI schemas of the decimal column types
+//!
+//! `Damage`, `ReloadTime` and the other decimal aliases are all
+//! `rust_decimal::Decimal`, which serializes as a string to keep its exact
+//! value. Each alias gets its own named schema here so the document states the
+//! SQL precision and scale of every decimal field. Model fields refer to these
+//! through `#[schema(value_type = ...)]`.
+
+use utoipa::openapi::schema::{ObjectBuilder, Schema, SchemaFormat, Type};
+use utoipa::openapi::RefOr;
+use utoipa::{PartialSchema, ToSchema};
+
+/// Format of every decimal schema
+pub const DECIMAL_FORMAT: &str = "decimal";
+
+macro_rules! decimal_schema {
+    ($(#[$meta:meta])* $name:ident, $sql:literal, $pattern:literal, $example:literal) => {
+        $(#[$meta])*
+        pub struct $name;
+
+        impl PartialSchema for $name {
+            fn schema() -> RefOr<Schema> {
+                ObjectBuilder::new()
+                    .schema_type(Type::String)
+                    .format(Some(SchemaFormat::Custom(DECIMAL_FORMAT.to_string())))
+                    .pattern(Some($pattern))
+                    .description(Some(concat!(
+                        $sql,
+                        ", serialized as a string to preserve its exact value"
+                    )))
+                    .examples([$example])
+                    .into()
+            }
+        }
+
+        impl ToSchema for $name {}
+    };
+}
+
+decimal_schema!(
+    /// `Damage`: DECIMAL(5,1), up to 9999.9
+    Damage,
+    "DECIMAL(5,1)",
+    r"^-?\d{1,4}(\.\d)?$",
+    "25.0"
+);
+
+decimal_schema!(
+    /// `ReloadTime` in seconds: DECIMAL(4,2), up to 99.99
+    ReloadTime,
+    "DECIMAL(4,2)",
+    r"^-?\d{1,2}(\.\d{1,2})?$",
+    "2.45"
+);
+
+decimal_schema!(
+    /// `HeadshotMultiplier`: DECIMAL(3,1), up to 99.9
+    HeadshotMultiplier,
+    "DECIMAL(3,1)",
+    r"^-?\d{1,2}(\.\d)?$",
+    "1.5"
+);
+
+decimal_schema!(
+    /// `Modifier`: DECIMAL(3,2), up to 9.99
+    Modifier,
+    "DECIMAL(3,2)",
+    r"^-?\d(\.\d{1,2})?$",
+    "0.85"
+);
+
+decimal_schema!(
+    /// `Angle` in degrees: DECIMAL(5,3), up to 99.999
+    Angle,
+    "DECIMAL(5,3)",
+    r"^-?\d{1,2}(\.\d{1,3})?$",
+    "0.325"
+);
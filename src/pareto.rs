@@ -0,0 +1,222 @@
+//! Pareto-frontier analysis of weapon configurations
+//!
+//! A configuration is on the frontier when no other configuration of the
+//! same weapon is at least as good on every chosen metric and strictly
+//! better on one.
+
+use crate::ballistics;
+use crate::models::{TargetProfile, WeaponConfig};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Axis used to compare configurations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParetoMetric {
+    /// Time to kill in milliseconds at a range (lower is better)
+    TtkAt(i16),
+    /// Damage per hit at a range
+    DamageAt(i16),
+    /// Magazine size
+    MagazineSize,
+    /// Projectile velocity
+    Velocity,
+    /// Sustained fire rate, see `ballistics::fire_rate`
+    FireRate,
+    /// Tactical reload time, falling back to empty reload (lower is better)
+    ReloadTime,
+}
+
+impl ParetoMetric {
+    /// Whether a larger value wins on this axis
+    pub fn higher_is_better(&self) -> bool {
+        !matches!(self, ParetoMetric::TtkAt(_) | ParetoMetric::ReloadTime)
+    }
+
+    /// Value of this metric for a configuration against a target with `health`
+    fn value(&self, config: &WeaponConfig, health: Decimal) -> Option<f64> {
+        let ammo = config.ammo_stats.as_ref();
+        match *self {
+            ParetoMetric::TtkAt(range) => ballistics::time_to_kill_ms(
+                config.bullets_to_kill(range, health)?,
+                config.fire_rate()?,
+            ),
+            ParetoMetric::DamageAt(range) => config.damage_at(range)?.to_f64(),
+            ParetoMetric::MagazineSize => ammo.map(|stats| f64::from(stats.magazine_size)),
+            ParetoMetric::Velocity => Some(f64::from(config.velocity)),
+            ParetoMetric::FireRate => config.fire_rate().map(f64::from),
+            ParetoMetric::ReloadTime => ammo
+                .and_then(|stats| stats.tactical_reload_time.or(stats.empty_reload_time))?
+                .to_f64(),
+        }
+    }
+}
+
+impl fmt::Display for ParetoMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParetoMetric::TtkAt(range) => write!(f, "TTK at {}m", range),
+            ParetoMetric::DamageAt(range) => write!(f, "damage at {}m", range),
+            ParetoMetric::MagazineSize => write!(f, "magazine size"),
+            ParetoMetric::Velocity => write!(f, "velocity"),
+            ParetoMetric::FireRate => write!(f, "fire rate"),
+            ParetoMetric::ReloadTime => write!(f, "reload time"),
+        }
+    }
+}
+
+/// Non-dominated configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParetoConfig {
+    pub config_id: i32,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    /// Metric values in the order the metrics were requested
+    pub values: Vec<Option<f64>>,
+    /// Metrics on which this configuration is best of all configurations
+    pub wins: Vec<ParetoMetric>,
+}
+
+/// Orient a value so that larger always wins; missing values lose
+fn score(metric: &ParetoMetric, value: Option<f64>) -> f64 {
+    match value {
+        Some(value) if metric.higher_is_better() => value,
+        Some(value) => -value,
+        None => f64::NEG_INFINITY,
+    }
+}
+
+/// Whether `a` dominates `b`
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Keep only the non-dominated configurations
+pub fn frontier(
+    configs: Vec<WeaponConfig>,
+    metrics: &[ParetoMetric],
+    target: &TargetProfile,
+) -> Vec<ParetoConfig> {
+    let health = target.effective_health();
+    let values: Vec<Vec<Option<f64>>> = configs
+        .iter()
+        .map(|config| {
+            metrics
+                .iter()
+                .map(|metric| metric.value(config, health))
+                .collect()
+        })
+        .collect();
+
+    let scores: Vec<Vec<f64>> = values
+        .iter()
+        .map(|row| {
+            metrics
+                .iter()
+                .zip(row)
+                .map(|(metric, value)| score(metric, *value))
+                .collect()
+        })
+        .collect();
+
+    let best: Vec<f64> = (0..metrics.len())
+        .map(|idx| {
+            scores
+                .iter()
+                .map(|row| row[idx])
+                .fold(f64::NEG_INFINITY, f64::max)
+        })
+        .collect();
+
+    configs
+        .into_iter()
+        .zip(values)
+        .enumerate()
+        .filter(|(idx, _)| !scores.iter().any(|other| dominates(other, &scores[*idx])))
+        .map(|(idx, (config, values))| {
+            let wins = metrics
+                .iter()
+                .zip(&best)
+                .enumerate()
+                .filter(|(axis, (_, best))| scores[idx][*axis] == **best && best.is_finite())
+                .map(|(_, (metric, _))| *metric)
+                .collect();
+
+            ParetoConfig {
+                config_id: config.config_id,
+                barrel_name: config.barrel_name,
+                ammo_type_name: config.ammo_type_name,
+                values,
+                wins,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(config_id: i32, velocity: i16, rpm_auto: Option<i16>) -> WeaponConfig {
+        WeaponConfig {
+            config_id,
+            weapon_name: "AK-24".to_string(),
+            barrel_name: format!("Barrel {}", config_id),
+            ammo_type_name: "Standard".to_string(),
+            velocity,
+            rpm_single: None,
+            rpm_burst: None,
+            rpm_auto,
+            recoil_vertical: None,
+            recoil_horizontal: None,
+            recoil_first_shot_multiplier: None,
+            spread_ads: None,
+            spread_hipfire: None,
+            spread_bloom_per_shot: None,
+            dropoffs: Vec::new(),
+            ammo_stats: None,
+        }
+    }
+
+    #[test]
+    fn test_frontier_drops_dominated_configs() {
+        let configs = vec![
+            config(1, 900, Some(600)),
+            config(2, 700, Some(800)),
+            config(3, 700, Some(600)),
+        ];
+        let metrics = [ParetoMetric::Velocity, ParetoMetric::FireRate];
+        let frontier = frontier(configs, &metrics, &TargetProfile::default());
+
+        let ids: Vec<_> = frontier.iter().map(|config| config.config_id).collect();
+        assert_eq!(ids, [1, 2]);
+        assert_eq!(frontier[0].wins, [ParetoMetric::Velocity]);
+        assert_eq!(frontier[1].wins, [ParetoMetric::FireRate]);
+        assert_eq!(frontier[0].values, [Some(900.0), Some(600.0)]);
+    }
+
+    #[test]
+    fn test_missing_values_never_win() {
+        let configs = vec![config(1, 900, None), config(2, 900, None)];
+        let frontier = frontier(
+            configs,
+            &[ParetoMetric::FireRate],
+            &TargetProfile::default(),
+        );
+
+        // Neither dominates the other, but no one is best on a missing metric
+        assert_eq!(frontier.len(), 2);
+        assert!(frontier.iter().all(|config| config.wins.is_empty()));
+    }
+
+    #[test]
+    fn test_lower_is_better_metrics() {
+        assert!(!ParetoMetric::TtkAt(10).higher_is_better());
+        assert!(!ParetoMetric::ReloadTime.higher_is_better());
+        assert!(ParetoMetric::Velocity.higher_is_better());
+        assert!(dominates(&[-300.0, 2.0], &[-400.0, 2.0]));
+        assert!(!dominates(&[-300.0, 2.0], &[-300.0, 2.0]));
+    }
+}
@@ -0,0 +1,802 @@
+//! Markdown and HTML rendering of query results
+//!
+//! Result rows implement `Columns`, which names their columns and produces one
+//! `Cell` per column. A `Table` selects the columns to show and how many decimal
+//! places to print; a `StatCard` lays out one weapon's configurations as a
+//! summary followed by its damage at every dropoff range. `markdown` and `html`
+//! render either, so wiki exports, bots and the MCP server share one layout.
+
+use crate::ballistics;
+use crate::models::{
+    BestConfigInCategory, DamageAtRange, WeaponAmmoStatsWithNames, WeaponConfig, WeaponDetails,
+};
+use crate::{Result, StatsError};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+/// Most decimal places `Table` and `StatCard` print, the largest scale of a `Decimal`
+pub const MAX_DECIMAL_PLACES: u32 = Decimal::MAX_SCALE;
+
+/// Value of one table cell
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    Text(String),
+    Integer(i64),
+    Decimal(Decimal),
+    /// Derived value, printed with one decimal place unless configured otherwise
+    Float(f64),
+    /// Damage by range, printed as `25.0 @ 0m, 20.0 @ 40m`
+    Dropoffs(Vec<(i16, Decimal)>),
+    Empty,
+}
+
+impl Cell {
+    fn is_text(&self) -> bool {
+        matches!(self, Cell::Text(_) | Cell::Dropoffs(_))
+    }
+
+    fn format(&self, decimal_places: Option<u32>) -> String {
+        match self {
+            Cell::Text(text) => text.clone(),
+            Cell::Integer(value) => value.to_string(),
+            Cell::Decimal(value) => format_decimal(*value, decimal_places),
+            Cell::Float(value) => format!("{:.*}", decimal_places.unwrap_or(1) as usize, value),
+            Cell::Dropoffs(dropoffs) => dropoffs
+                .iter()
+                .map(|(range, damage)| {
+                    format!("{} @ {}m", format_decimal(*damage, decimal_places), range)
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+            Cell::Empty => String::new(),
+        }
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Cell::Text(value.to_string())
+    }
+}
+
+impl From<&String> for Cell {
+    fn from(value: &String) -> Self {
+        Cell::Text(value.clone())
+    }
+}
+
+impl From<i16> for Cell {
+    fn from(value: i16) -> Self {
+        Cell::Integer(value.into())
+    }
+}
+
+impl From<i32> for Cell {
+    fn from(value: i32) -> Self {
+        Cell::Integer(value.into())
+    }
+}
+
+impl From<Decimal> for Cell {
+    fn from(value: Decimal) -> Self {
+        Cell::Decimal(value)
+    }
+}
+
+impl From<f64> for Cell {
+    fn from(value: f64) -> Self {
+        Cell::Float(value)
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Cell::Empty, Into::into)
+    }
+}
+
+/// Decimal as stored, or rounded half away from zero and padded to `places`
+fn format_decimal(value: Decimal, places: Option<u32>) -> String {
+    match places {
+        Some(places) => format!(
+            "{:.*}",
+            places as usize,
+            value.round_dp_with_strategy(places, RoundingStrategy::MidpointAwayFromZero)
+        ),
+        None => value.to_string(),
+    }
+}
+
+/// A result row that can be rendered as a table row
+pub trait Columns {
+    /// Every column as `(name, header)`, in display order
+    const COLUMNS: &'static [(&'static str, &'static str)];
+
+    /// Columns shown when none are selected
+    const DEFAULT_COLUMNS: &'static [&'static str];
+
+    /// Cell of `column`, one of the names in `COLUMNS`
+    fn cell(&self, column: &str) -> Cell;
+}
+
+impl Columns for DamageAtRange {
+    const COLUMNS: &'static [(&'static str, &'static str)] = &[
+        ("weapon_name", "Weapon"),
+        ("barrel_name", "Barrel"),
+        ("ammo_type_name", "Ammo"),
+        ("effective_range", "Range (m)"),
+        ("damage", "Damage"),
+        ("velocity", "Velocity (m/s)"),
+        ("fire_rate", "Fire rate (RPM)"),
+        ("rpm_single", "Single RPM"),
+        ("rpm_burst", "Burst RPM"),
+        ("rpm_auto", "Auto RPM"),
+        ("headshot_multiplier", "Headshot multiplier"),
+        ("upper_chest_multiplier", "Upper chest multiplier"),
+        ("limb_multiplier", "Limb multiplier"),
+        ("pellet_count", "Pellets"),
+        ("flight_time_ms", "Flight time (ms)"),
+        ("bullet_drop", "Bullet drop (m)"),
+        ("lead_distance", "Lead (m)"),
+        ("effective_ttk_ms", "TTK (ms)"),
+    ];
+
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "barrel_name",
+        "ammo_type_name",
+        "effective_range",
+        "damage",
+        "velocity",
+        "fire_rate",
+        "headshot_multiplier",
+    ];
+
+    fn cell(&self, column: &str) -> Cell {
+        match column {
+            "weapon_name" => (&self.weapon_name).into(),
+            "barrel_name" => (&self.barrel_name).into(),
+            "ammo_type_name" => (&self.ammo_type_name).into(),
+            "effective_range" => self.effective_range.into(),
+            "damage" => self.damage.into(),
+            "velocity" => self.velocity.into(),
+            "fire_rate" => ballistics::fire_rate(self.rpm_single, self.rpm_auto).into(),
+            "rpm_single" => self.rpm_single.into(),
+            "rpm_burst" => self.rpm_burst.into(),
+            "rpm_auto" => self.rpm_auto.into(),
+            "headshot_multiplier" => self.headshot_multiplier.into(),
+            "upper_chest_multiplier" => self.upper_chest_multiplier.into(),
+            "limb_multiplier" => self.limb_multiplier.into(),
+            "pellet_count" => self.pellet_count.into(),
+            "flight_time_ms" => self.flight_time_ms.into(),
+            "bullet_drop" => self.bullet_drop.into(),
+            "lead_distance" => self.lead_distance.into(),
+            "effective_ttk_ms" => self.effective_ttk_ms.into(),
+            _ => Cell::Empty,
+        }
+    }
+}
+
+impl Columns for BestConfigInCategory {
+    const COLUMNS: &'static [(&'static str, &'static str)] = &[
+        ("config_id", "Config"),
+        ("category_name", "Category"),
+        ("weapon_name", "Weapon"),
+        ("barrel_name", "Barrel"),
+        ("ammo_type_name", "Ammo"),
+        ("effective_range", "Range (m)"),
+        ("damage", "Damage"),
+        ("velocity", "Velocity (m/s)"),
+        ("fire_rate", "Fire rate (RPM)"),
+        ("rpm_single", "Single RPM"),
+        ("rpm_burst", "Burst RPM"),
+        ("rpm_auto", "Auto RPM"),
+        ("magazine_size", "Magazine"),
+        ("empty_reload_time", "Empty reload (s)"),
+        ("tactical_reload_time", "Tactical reload (s)"),
+        ("headshot_multiplier", "Headshot multiplier"),
+        ("recoil_vertical", "Vertical recoil"),
+        ("recoil_horizontal", "Horizontal recoil"),
+        ("recoil_first_shot_multiplier", "First shot recoil"),
+        ("spread_ads", "ADS spread"),
+        ("spread_hipfire", "Hipfire spread"),
+        ("spread_bloom_per_shot", "Bloom per shot"),
+    ];
+
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "weapon_name",
+        "barrel_name",
+        "ammo_type_name",
+        "effective_range",
+        "damage",
+        "velocity",
+        "fire_rate",
+        "magazine_size",
+    ];
+
+    fn cell(&self, column: &str) -> Cell {
+        match column {
+            "config_id" => self.config_id.into(),
+            "category_name" => (&self.category_name).into(),
+            "weapon_name" => (&self.weapon_name).into(),
+            "barrel_name" => (&self.barrel_name).into(),
+            "ammo_type_name" => (&self.ammo_type_name).into(),
+            "effective_range" => self.effective_range.into(),
+            "damage" => self.damage.into(),
+            "velocity" => self.velocity.into(),
+            "fire_rate" => ballistics::fire_rate(self.rpm_single, self.rpm_auto).into(),
+            "rpm_single" => self.rpm_single.into(),
+            "rpm_burst" => self.rpm_burst.into(),
+            "rpm_auto" => self.rpm_auto.into(),
+            "magazine_size" => self.magazine_size.into(),
+            "empty_reload_time" => self.empty_reload_time.into(),
+            "tactical_reload_time" => self.tactical_reload_time.into(),
+            "headshot_multiplier" => self.headshot_multiplier.into(),
+            "recoil_vertical" => self.recoil_vertical.into(),
+            "recoil_horizontal" => self.recoil_horizontal.into(),
+            "recoil_first_shot_multiplier" => self.recoil_first_shot_multiplier.into(),
+            "spread_ads" => self.spread_ads.into(),
+            "spread_hipfire" => self.spread_hipfire.into(),
+            "spread_bloom_per_shot" => self.spread_bloom_per_shot.into(),
+            _ => Cell::Empty,
+        }
+    }
+}
+
+impl Columns for WeaponAmmoStatsWithNames {
+    const COLUMNS: &'static [(&'static str, &'static str)] = &[
+        ("weapon_name", "Weapon"),
+        ("ammo_type_name", "Ammo"),
+        ("magazine_size", "Magazine"),
+        ("empty_reload_time", "Empty reload (s)"),
+        ("tactical_reload_time", "Tactical reload (s)"),
+        ("headshot_multiplier", "Headshot multiplier"),
+        ("pellet_count", "Pellets"),
+        ("upper_chest_multiplier", "Upper chest multiplier"),
+        ("limb_multiplier", "Limb multiplier"),
+    ];
+
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "ammo_type_name",
+        "magazine_size",
+        "empty_reload_time",
+        "tactical_reload_time",
+        "headshot_multiplier",
+        "pellet_count",
+    ];
+
+    fn cell(&self, column: &str) -> Cell {
+        match column {
+            "weapon_name" => (&self.weapon_name).into(),
+            "ammo_type_name" => (&self.ammo_type_name).into(),
+            "magazine_size" => self.magazine_size.into(),
+            "empty_reload_time" => self.empty_reload_time.into(),
+            "tactical_reload_time" => self.tactical_reload_time.into(),
+            "headshot_multiplier" => self.headshot_multiplier.into(),
+            "pellet_count" => self.pellet_count.into(),
+            "upper_chest_multiplier" => self.upper_chest_multiplier.into(),
+            "limb_multiplier" => self.limb_multiplier.into(),
+            _ => Cell::Empty,
+        }
+    }
+}
+
+/// Grouped configurations, one row per configuration with its dropoffs in one cell
+impl Columns for WeaponConfig {
+    const COLUMNS: &'static [(&'static str, &'static str)] = &[
+        ("config_id", "Config"),
+        ("weapon_name", "Weapon"),
+        ("barrel_name", "Barrel"),
+        ("ammo_type_name", "Ammo"),
+        ("velocity", "Velocity (m/s)"),
+        ("fire_rate", "Fire rate (RPM)"),
+        ("rpm_single", "Single RPM"),
+        ("rpm_burst", "Burst RPM"),
+        ("rpm_auto", "Auto RPM"),
+        ("recoil_vertical", "Vertical recoil"),
+        ("recoil_horizontal", "Horizontal recoil"),
+        ("recoil_first_shot_multiplier", "First shot recoil"),
+        ("spread_ads", "ADS spread"),
+        ("spread_hipfire", "Hipfire spread"),
+        ("spread_bloom_per_shot", "Bloom per shot"),
+        ("magazine_size", "Magazine"),
+        ("headshot_multiplier", "Headshot multiplier"),
+        ("dropoffs", "Damage"),
+    ];
+
+    const DEFAULT_COLUMNS: &'static [&'static str] = &[
+        "barrel_name",
+        "ammo_type_name",
+        "velocity",
+        "fire_rate",
+        "magazine_size",
+        "dropoffs",
+    ];
+
+    fn cell(&self, column: &str) -> Cell {
+        match column {
+            "config_id" => self.config_id.into(),
+            "weapon_name" => (&self.weapon_name).into(),
+            "barrel_name" => (&self.barrel_name).into(),
+            "ammo_type_name" => (&self.ammo_type_name).into(),
+            "velocity" => self.velocity.into(),
+            "fire_rate" => self.fire_rate().into(),
+            "rpm_single" => self.rpm_single.into(),
+            "rpm_burst" => self.rpm_burst.into(),
+            "rpm_auto" => self.rpm_auto.into(),
+            "recoil_vertical" => self.recoil_vertical.into(),
+            "recoil_horizontal" => self.recoil_horizontal.into(),
+            "recoil_first_shot_multiplier" => self.recoil_first_shot_multiplier.into(),
+            "spread_ads" => self.spread_ads.into(),
+            "spread_hipfire" => self.spread_hipfire.into(),
+            "spread_bloom_per_shot" => self.spread_bloom_per_shot.into(),
+            "magazine_size" => self
+                .ammo_stats
+                .as_ref()
+                .map(|stats| stats.magazine_size)
+                .into(),
+            "headshot_multiplier" => self
+                .ammo_stats
+                .as_ref()
+                .map(|stats| stats.headshot_multiplier)
+                .into(),
+            "dropoffs" => Cell::Dropoffs(
+                self.dropoffs
+                    .iter()
+                    .map(|dropoff| (dropoff.range, dropoff.damage))
+                    .collect(),
+            ),
+            _ => Cell::Empty,
+        }
+    }
+}
+
+/// Formatted cells with their headers, ready to be written out
+struct Grid {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    /// Right-align columns without text cells
+    numeric: Vec<bool>,
+}
+
+impl Grid {
+    fn new(headers: Vec<String>, cells: Vec<Vec<Cell>>, decimal_places: Option<u32>) -> Self {
+        let numeric = (0..headers.len())
+            .map(|idx| !cells.iter().any(|row| row[idx].is_text()))
+            .collect();
+        let rows = cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.format(decimal_places)).collect())
+            .collect();
+        Self {
+            headers,
+            rows,
+            numeric,
+        }
+    }
+
+    /// Pipe table with columns padded to equal width, readable as plain text too
+    fn write_markdown(&self, out: &mut String) {
+        let headers: Vec<_> = self
+            .headers
+            .iter()
+            .map(|text| escape_markdown(text))
+            .collect();
+        let rows: Vec<Vec<_>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|text| escape_markdown(text)).collect())
+            .collect();
+        let widths: Vec<_> = (0..headers.len())
+            .map(|idx| {
+                rows.iter()
+                    .map(|row| row[idx].chars().count())
+                    .chain([headers[idx].chars().count(), 3])
+                    .max()
+                    .unwrap_or(3)
+            })
+            .collect();
+
+        let write_row = |out: &mut String, row: &[String]| {
+            out.push('|');
+            for (idx, text) in row.iter().enumerate() {
+                let width = widths[idx];
+                if self.numeric[idx] {
+                    let _ = write!(out, " {:>width$} |", text);
+                } else {
+                    let _ = write!(out, " {:<width$} |", text);
+                }
+            }
+            out.push('\n');
+        };
+
+        write_row(out, &headers);
+        out.push('|');
+        for (idx, width) in widths.iter().enumerate() {
+            if self.numeric[idx] {
+                let _ = write!(out, " {}: |", "-".repeat(width - 1));
+            } else {
+                let _ = write!(out, " {} |", "-".repeat(*width));
+            }
+        }
+        out.push('\n');
+        for row in &rows {
+            write_row(out, row);
+        }
+    }
+
+    fn write_html(&self, out: &mut String) {
+        let align = |idx: usize| {
+            if self.numeric[idx] {
+                " style=\"text-align: right\""
+            } else {
+                ""
+            }
+        };
+
+        out.push_str("<table>\n<thead>\n<tr>");
+        for (idx, header) in self.headers.iter().enumerate() {
+            let _ = write!(out, "<th{}>{}</th>", align(idx), escape_html(header));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+        for row in &self.rows {
+            out.push_str("<tr>");
+            for (idx, text) in row.iter().enumerate() {
+                let _ = write!(out, "<td{}>{}</td>", align(idx), escape_html(text));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+}
+
+fn escape_markdown(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// Something `markdown` and `html` can render
+pub trait Render {
+    fn write_markdown(&self, out: &mut String);
+    fn write_html(&self, out: &mut String);
+}
+
+/// Render as Markdown, e.g. for wiki pages and Discord posts
+pub fn markdown(item: &impl Render) -> String {
+    let mut out = String::new();
+    item.write_markdown(&mut out);
+    out
+}
+
+/// Render as an HTML fragment
+pub fn html(item: &impl Render) -> String {
+    let mut out = String::new();
+    item.write_html(&mut out);
+    out
+}
+
+/// Rows rendered with a selection of their columns
+#[derive(Debug, Clone)]
+pub struct Table<'a, T> {
+    rows: &'a [T],
+    columns: Vec<(&'static str, &'static str)>,
+    decimal_places: Option<u32>,
+}
+
+impl<'a, T: Columns> Table<'a, T> {
+    /// Table of `rows` with the default columns and decimals as stored
+    pub fn new(rows: &'a [T]) -> Self {
+        let columns = T::DEFAULT_COLUMNS
+            .iter()
+            .filter_map(|name| T::COLUMNS.iter().find(|(column, _)| column == name))
+            .copied()
+            .collect();
+        Self {
+            rows,
+            columns,
+            decimal_places: None,
+        }
+    }
+
+    /// Show `columns` in the given order instead of the defaults
+    pub fn with_columns<S: AsRef<str>>(mut self, columns: &[S]) -> Result<Self> {
+        self.columns = columns
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                T::COLUMNS
+                    .iter()
+                    .find(|(column, _)| *column == name)
+                    .copied()
+                    .ok_or_else(|| StatsError::UnknownColumn {
+                        column: name.to_string(),
+                        expected: T::COLUMNS
+                            .iter()
+                            .map(|(column, _)| *column)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    })
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Round decimals half away from zero and pad them to `places`, at most
+    /// `MAX_DECIMAL_PLACES`
+    pub fn with_decimal_places(mut self, places: u32) -> Self {
+        self.decimal_places = Some(places.min(MAX_DECIMAL_PLACES));
+        self
+    }
+
+    fn grid(&self) -> Grid {
+        let headers = self
+            .columns
+            .iter()
+            .map(|(_, header)| header.to_string())
+            .collect();
+        let cells = self
+            .rows
+            .iter()
+            .map(|row| {
+                self.columns
+                    .iter()
+                    .map(|(column, _)| row.cell(column))
+                    .collect()
+            })
+            .collect();
+        Grid::new(headers, cells, self.decimal_places)
+    }
+}
+
+impl<T: Columns> Render for Table<'_, T> {
+    fn write_markdown(&self, out: &mut String) {
+        self.grid().write_markdown(out);
+    }
+
+    fn write_html(&self, out: &mut String) {
+        self.grid().write_html(out);
+    }
+}
+
+/// Per-weapon summary followed by the damage of every configuration by range
+#[derive(Debug, Clone)]
+pub struct StatCard<'a> {
+    weapon_name: &'a str,
+    category_name: Option<&'a str>,
+    configs: &'a [WeaponConfig],
+    decimal_places: Option<u32>,
+}
+
+impl<'a> StatCard<'a> {
+    /// Card of a weapon's grouped configurations
+    pub fn new(weapon_name: &'a str, configs: &'a [WeaponConfig]) -> Self {
+        Self {
+            weapon_name,
+            category_name: None,
+            configs,
+            decimal_places: None,
+        }
+    }
+
+    /// Card of a weapon with its category
+    pub fn from_details(details: &'a WeaponDetails) -> Self {
+        Self::new(&details.weapon.weapon_name, &details.configurations)
+            .with_category(&details.category_name)
+    }
+
+    pub fn with_category(mut self, category_name: &'a str) -> Self {
+        self.category_name = Some(category_name);
+        self
+    }
+
+    /// Round decimals half away from zero and pad them to `places`, at most
+    /// `MAX_DECIMAL_PLACES`
+    pub fn with_decimal_places(mut self, places: u32) -> Self {
+        self.decimal_places = Some(places.min(MAX_DECIMAL_PLACES));
+        self
+    }
+
+    /// Spread of a stat across configurations, e.g. `18.0–25.0`
+    fn span<T: Ord + Copy + Into<Cell>>(&self, values: impl Iterator<Item = T>) -> Option<Cell> {
+        let values: Vec<_> = values.collect();
+        let min = *values.iter().min()?;
+        let max = *values.iter().max()?;
+        let min_text = Into::<Cell>::into(min).format(self.decimal_places);
+        if min == max {
+            return Some(Cell::Text(min_text));
+        }
+        let max_text = Into::<Cell>::into(max).format(self.decimal_places);
+        Some(Cell::Text(format!("{}–{}", min_text, max_text)))
+    }
+
+    fn summary(&self) -> Grid {
+        let configs = self.configs;
+        let stats = [
+            (
+                "Configurations",
+                Some(Cell::Text(configs.len().to_string())),
+            ),
+            (
+                "Damage",
+                self.span(
+                    configs
+                        .iter()
+                        .flat_map(|config| config.dropoffs.iter().map(|dropoff| dropoff.damage)),
+                ),
+            ),
+            (
+                "Velocity (m/s)",
+                self.span(configs.iter().map(|config| config.velocity)),
+            ),
+            (
+                "Fire rate (RPM)",
+                self.span(configs.iter().filter_map(WeaponConfig::fire_rate)),
+            ),
+            (
+                "Magazine",
+                self.span(
+                    configs
+                        .iter()
+                        .filter_map(|config| config.ammo_stats.as_ref())
+                        .map(|stats| stats.magazine_size),
+                ),
+            ),
+        ];
+        let cells = stats
+            .into_iter()
+            .filter_map(|(name, value)| Some(vec![Cell::from(name), value?]))
+            .collect();
+        Grid::new(
+            vec!["Stat".to_string(), "Value".to_string()],
+            cells,
+            self.decimal_places,
+        )
+    }
+
+    /// One row per configuration, one column per range any of them drops off at
+    fn damage_by_range(&self) -> Grid {
+        let ranges: BTreeSet<i16> = self
+            .configs
+            .iter()
+            .flat_map(|config| config.dropoffs.iter().map(|dropoff| dropoff.range))
+            .collect();
+
+        let headers = ["Barrel".to_string(), "Ammo".to_string()]
+            .into_iter()
+            .chain(ranges.iter().map(|range| format!("{} m", range)))
+            .collect();
+        let cells = self
+            .configs
+            .iter()
+            .map(|config| {
+                [
+                    (&config.barrel_name).into(),
+                    (&config.ammo_type_name).into(),
+                ]
+                .into_iter()
+                .chain(ranges.iter().map(|range| config.damage_at(*range).into()))
+                .collect()
+            })
+            .collect();
+        Grid::new(headers, cells, self.decimal_places)
+    }
+}
+
+impl Render for StatCard<'_> {
+    fn write_markdown(&self, out: &mut String) {
+        let _ = writeln!(out, "## {}\n", escape_markdown(self.weapon_name));
+        if let Some(category_name) = self.category_name {
+            let _ = writeln!(out, "*{}*\n", escape_markdown(category_name));
+        }
+        self.summary().write_markdown(out);
+        out.push_str("\n### Damage by range\n\n");
+        self.damage_by_range().write_markdown(out);
+    }
+
+    fn write_html(&self, out: &mut String) {
+        out.push_str("<section class=\"stat-card\">\n");
+        let _ = writeln!(out, "<h2>{}</h2>", escape_html(self.weapon_name));
+        if let Some(category_name) = self.category_name {
+            let _ = writeln!(out, "<p>{}</p>", escape_html(category_name));
+        }
+        self.summary().write_html(out);
+        out.push_str("<h3>Damage by range</h3>\n");
+        self.damage_by_range().write_html(out);
+        out.push_str("</section>\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(barrel_name: &str, damage: Decimal) -> DamageAtRange {
+        DamageAtRange {
+            weapon_name: "M5A3".to_string(),
+            barrel_name: barrel_name.to_string(),
+            ammo_type_name: "Standard".to_string(),
+            effective_range: 40,
+            damage,
+            velocity: 650,
+            rpm_single: None,
+            rpm_burst: None,
+            rpm_auto: Some(900),
+            headshot_multiplier: None,
+            upper_chest_multiplier: None,
+            limb_multiplier: None,
+            pellet_count: None,
+            flight_time_ms: None,
+            bullet_drop: None,
+            lead_distance: None,
+            effective_ttk_ms: None,
+        }
+    }
+
+    #[test]
+    fn test_markdown_alignment_and_rounding() {
+        let rows = [
+            row("Factory", Decimal::new(2245, 2)),
+            row("Short | CQB", Decimal::new(20, 0)),
+        ];
+        let table = Table::new(&rows)
+            .with_columns(&["barrel_name", "damage"])
+            .unwrap()
+            .with_decimal_places(1);
+        let rendered = markdown(&table);
+        let lines: Vec<_> = rendered.lines().collect();
+
+        // Text left-aligned and numbers right-aligned, halves rounded away from zero
+        assert_eq!(lines.len(), rows.len() + 2);
+        assert!(lines[0].starts_with("| Barrel ") && lines[0].ends_with(" Damage |"));
+        assert!(lines[1].starts_with("| ---") && lines[1].ends_with("-: |"));
+        assert!(lines[2].starts_with("| Factory ") && lines[2].ends_with(" 22.5 |"));
+        assert!(lines[3].starts_with("| Short \\| CQB ") && lines[3].ends_with(" 20.0 |"));
+        let width = lines[0].chars().count();
+        assert!(lines.iter().all(|line| line.chars().count() == width));
+    }
+
+    #[test]
+    fn test_unknown_column() {
+        let error = Table::<DamageAtRange>::new(&[])
+            .with_columns(&["dps"])
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Unknown column 'dps'"));
+    }
+
+    #[test]
+    fn test_decimal_places_capped() {
+        let rows = [row("Factory", Decimal::new(2245, 2))];
+        let widest = markdown(&Table::new(&rows).with_decimal_places(u32::MAX));
+        let capped = markdown(&Table::new(&rows).with_decimal_places(MAX_DECIMAL_PLACES));
+        assert_eq!(widest, capped);
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        let rows = [row("Factory & <Long>", Decimal::new(25, 0))];
+        let table = html(&Table::new(&rows));
+        assert!(table.starts_with("<table>\n<thead>\n<tr><th>Barrel</th>"));
+        assert!(table.contains("<td>Factory &amp; &lt;Long&gt;</td>"));
+        assert!(table.contains("<td style=\"text-align: right\">25</td>"));
+
+        let card = html(&StatCard::new("M5A3 <SMG>", &[]));
+        assert!(card.starts_with("<section class=\"stat-card\">\n<h2>M5A3 &lt;SMG&gt;</h2>"));
+        assert!(card.ends_with("</section>\n"));
+    }
+}
@@ -0,0 +1,315 @@
+//! Accuracy-weighted time-to-kill simulation
+//!
+//! Raw TTK assumes every bullet hits the torso. This module runs a seeded
+//! Monte Carlo simulation over hit probability and hit locations to estimate
+//! the distribution of TTK for a configuration at a range.
+
+use crate::ballistics;
+use crate::models::{Damage, HeadshotMultiplier, Hitbox, HitboxMultipliers, TargetProfile};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+
+/// Rounding slack allowed when hit location ratios sum to 1
+const RATIO_SUM_TOLERANCE: f64 = 1e-9;
+
+/// Shooter accuracy and simulation settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccuracyProfile {
+    /// Probability that a shot hits the target
+    pub hit_probability: f64,
+    /// Fraction of hits that are headshots
+    pub headshot_ratio: f64,
+    /// Fraction of hits on the upper chest
+    #[serde(default)]
+    pub upper_chest_ratio: f64,
+    /// Fraction of hits on arms and legs
+    #[serde(default)]
+    pub limb_ratio: f64,
+    pub trials: u32,
+    pub seed: u64,
+}
+
+impl AccuracyProfile {
+    /// Create a new accuracy profile with defaults
+    pub fn new(hit_probability: f64, headshot_ratio: f64) -> Self {
+        Self {
+            hit_probability,
+            headshot_ratio,
+            upper_chest_ratio: 0.0,
+            limb_ratio: 0.0,
+            trials: 10_000,
+            seed: 0,
+        }
+    }
+
+    /// Set the upper chest and limb hit ratios; remaining hits land on the torso
+    pub fn with_hit_locations(mut self, upper_chest_ratio: f64, limb_ratio: f64) -> Self {
+        self.upper_chest_ratio = upper_chest_ratio;
+        self.limb_ratio = limb_ratio;
+        self
+    }
+
+    /// Set the number of simulated engagements
+    pub fn with_trials(mut self, trials: u32) -> Self {
+        self.trials = trials;
+        self
+    }
+
+    /// Set the random seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Check that probabilities and trial count are usable
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if !(self.hit_probability > 0.0 && self.hit_probability <= 1.0) {
+            return Err(format!(
+                "Hit probability must be in (0, 1], got {}",
+                self.hit_probability
+            ));
+        }
+        for (name, ratio) in [
+            ("Headshot", self.headshot_ratio),
+            ("Upper chest", self.upper_chest_ratio),
+            ("Limb", self.limb_ratio),
+        ] {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(format!("{} ratio must be in [0, 1], got {}", name, ratio));
+            }
+        }
+        let located = self.headshot_ratio + self.upper_chest_ratio + self.limb_ratio;
+        if located > 1.0 + RATIO_SUM_TOLERANCE {
+            return Err(format!(
+                "Hit location ratios must sum to at most 1, got {}",
+                located
+            ));
+        }
+        if self.trials == 0 {
+            return Err("At least one trial is required".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Deterministic SplitMix64 generator
+#[derive(Debug, Clone)]
+pub struct SimRng {
+    state: u64,
+}
+
+impl SimRng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform sample in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Sample the hitbox of a hit from the profile's location ratios
+    fn next_hitbox(&mut self, profile: &AccuracyProfile) -> Hitbox {
+        let roll = self.next_f64();
+        if roll < profile.headshot_ratio {
+            Hitbox::Head
+        } else if roll < profile.headshot_ratio + profile.upper_chest_ratio {
+            Hitbox::UpperChest
+        } else if roll < profile.headshot_ratio + profile.upper_chest_ratio + profile.limb_ratio {
+            Hitbox::Limb
+        } else {
+            Hitbox::Torso
+        }
+    }
+}
+
+/// Configuration stats used by one simulation
+#[derive(Debug, Clone)]
+pub struct Engagement {
+    /// Damage per projectile
+    pub damage: Damage,
+    /// Pellets per shot, all landing on the hitbox of a hit
+    pub pellet_count: Option<i16>,
+    pub multipliers: HitboxMultipliers,
+    pub rpm: i16,
+    /// Shots available before a reload, `None` for unlimited
+    pub magazine_size: Option<i16>,
+}
+
+/// Simulated TTK of one configuration at a range
+///
+/// Configurations without ammo stats or a fire rate cannot be simulated and
+/// are reported with `None` for both the multiplier and the distribution.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExpectedTtk {
+    pub weapon_name: String,
+    pub barrel_name: String,
+    pub ammo_type_name: String,
+    pub effective_range: i16,
+    pub damage: Damage,
+    pub headshot_multiplier: Option<HeadshotMultiplier>,
+    #[serde(flatten)]
+    pub distribution: Option<TtkDistribution>,
+}
+
+/// Summary of a simulated TTK distribution
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TtkDistribution {
+    /// Fraction of engagements that killed before the magazine ran dry
+    pub kill_probability: f64,
+    pub mean_ttk_ms: Option<f64>,
+    pub p50_ttk_ms: Option<f64>,
+    pub p90_ttk_ms: Option<f64>,
+    pub mean_shots: Option<f64>,
+}
+
+/// Upper bound on shots per engagement when the magazine size is unknown
+const MAX_SHOTS: u32 = 1_000;
+
+/// Nearest-rank percentile of sorted samples
+fn percentile(sorted: &[f64], percentile: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Simulate `profile.trials` engagements against `target`
+///
+/// Every call with the same profile, engagement and target yields the same result.
+pub fn simulate(
+    engagement: &Engagement,
+    profile: &AccuracyProfile,
+    target: &TargetProfile,
+) -> TtkDistribution {
+    let mut rng = SimRng::new(profile.seed);
+    let hit_damage = |hitbox: Hitbox| {
+        let damage = ballistics::shot_damage(engagement.damage, engagement.pellet_count);
+        (damage * target.hit_multiplier(hitbox, &engagement.multipliers))
+            .to_f64()
+            .unwrap_or(0.0)
+    };
+    let head = hit_damage(Hitbox::Head);
+    let upper_chest = hit_damage(Hitbox::UpperChest);
+    let torso = hit_damage(Hitbox::Torso);
+    let limb = hit_damage(Hitbox::Limb);
+    let health = target.effective_health().to_f64().unwrap_or(0.0);
+    let max_shots = engagement
+        .magazine_size
+        .map(|size| size.max(1) as u32)
+        .unwrap_or(MAX_SHOTS);
+
+    let mut ttks = Vec::with_capacity(profile.trials as usize);
+    let mut shots_total = 0u64;
+    for _ in 0..profile.trials {
+        let mut dealt = 0.0;
+        for shot in 0..max_shots {
+            if rng.next_f64() >= profile.hit_probability {
+                continue;
+            }
+            dealt += match rng.next_hitbox(profile) {
+                Hitbox::Head => head,
+                Hitbox::UpperChest => upper_chest,
+                Hitbox::Torso => torso,
+                Hitbox::Limb => limb,
+            };
+            if dealt >= health {
+                ttks.extend(ballistics::time_to_kill_ms(shot + 1, engagement.rpm));
+                shots_total += u64::from(shot) + 1;
+                break;
+            }
+        }
+    }
+
+    ttks.sort_by(f64::total_cmp);
+    let kills = ttks.len();
+    TtkDistribution {
+        kill_probability: kills as f64 / f64::from(profile.trials),
+        mean_ttk_ms: (kills > 0).then(|| ttks.iter().sum::<f64>() / kills as f64),
+        p50_ttk_ms: percentile(&ttks, 0.5),
+        p90_ttk_ms: percentile(&ttks, 0.9),
+        mean_shots: (kills > 0).then(|| shots_total as f64 / kills as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn engagement() -> Engagement {
+        Engagement {
+            damage: Decimal::new(25, 0),
+            pellet_count: None,
+            multipliers: HitboxMultipliers {
+                headshot: Decimal::new(15, 1),
+                upper_chest: None,
+                limb: None,
+            },
+            rpm: 600,
+            magazine_size: Some(30),
+        }
+    }
+
+    #[test]
+    fn test_simulate_is_deterministic() {
+        let profile = AccuracyProfile::new(0.6, 0.2)
+            .with_trials(2_000)
+            .with_seed(42);
+        let target = TargetProfile::default();
+        let first = simulate(&engagement(), &profile, &target);
+        let second = simulate(&engagement(), &profile, &target);
+
+        assert_eq!(first.mean_ttk_ms, second.mean_ttk_ms);
+        assert_eq!(first.p90_ttk_ms, second.p90_ttk_ms);
+        assert!(first.p50_ttk_ms <= first.p90_ttk_ms);
+    }
+
+    #[test]
+    fn test_perfect_accuracy_matches_raw_ttk() {
+        let profile = AccuracyProfile::new(1.0, 0.0).with_trials(10);
+        let target = TargetProfile::default();
+        let distribution = simulate(&engagement(), &profile, &target);
+
+        let btk = ballistics::bullets_to_kill(Decimal::new(25, 0), None, target.effective_health());
+        assert_eq!(distribution.kill_probability, 1.0);
+        assert_eq!(
+            distribution.p50_ttk_ms,
+            ballistics::time_to_kill_ms(btk.unwrap(), 600)
+        );
+        assert_eq!(distribution.mean_shots, btk.map(f64::from));
+    }
+
+    #[test]
+    fn test_unusable_profiles() {
+        assert!(AccuracyProfile::new(0.0, 0.5).validate().is_err());
+        assert!(AccuracyProfile::new(1.0, 1.5).validate().is_err());
+        assert!(AccuracyProfile::new(1.0, 0.0)
+            .with_trials(0)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_hit_location_ratios_summing_to_one() {
+        let profile = AccuracyProfile::new(1.0, 0.34).with_hit_locations(0.56, 0.1);
+        let located = profile.headshot_ratio + profile.upper_chest_ratio + profile.limb_ratio;
+        assert!(
+            located > 1.0,
+            "the split should overshoot in floating point"
+        );
+        assert_eq!(profile.validate(), Ok(()));
+
+        let profile = AccuracyProfile::new(1.0, 0.6).with_hit_locations(0.3, 0.3);
+        assert!(profile.validate().is_err());
+    }
+}
@@ -1,11 +1,8 @@
 //! Test utilities and mocks for the streaming API
 
-use crate::models::{
-    BestConfigInCategory, DamageAtRange, Weapon, WeaponAmmoStatsWithNames, WeaponConfigWithDropoffs,
-};
+use crate::models::{Weapon, WeaponConfigWithDropoffs};
 use crate::Result;
 use futures::Stream;
-use std::pin::Pin;
 
 /// Mock data for testing
 pub struct MockData;
@@ -42,6 +39,12 @@ impl MockData {
                 rpm_single: Some(600),
                 rpm_burst: Some(850),
                 rpm_auto: Some(600),
+                recoil_vertical: None,
+                recoil_horizontal: None,
+                recoil_first_shot_multiplier: None,
+                spread_ads: None,
+                spread_hipfire: None,
+                spread_bloom_per_shot: None,
                 range: 50,
                 damage: rust_decimal::Decimal::new(30, 0),
             },
@@ -54,6 +57,12 @@ impl MockData {
                 rpm_single: Some(600),
                 rpm_burst: Some(850),
                 rpm_auto: Some(600),
+                recoil_vertical: None,
+                recoil_horizontal: None,
+                recoil_first_shot_multiplier: None,
+                spread_ads: None,
+                spread_hipfire: None,
+                spread_bloom_per_shot: None,
                 range: 100,
                 damage: rust_decimal::Decimal::new(25, 0),
             },
@@ -67,6 +76,12 @@ pub struct MockStatsClient {
     configs: Vec<WeaponConfigWithDropoffs>,
 }
 
+impl Default for MockStatsClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MockStatsClient {
     pub fn new() -> Self {
         Self {
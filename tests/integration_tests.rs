@@ -1,6 +1,6 @@
 //! Integration tests for the BF2042 Stats library
 
-use bf2042_stats::{models::DatabaseConfig, database::DatabaseManager, StatsClient, Result};
+use bf2042_stats::{models::{DatabaseConfig, TargetProfile}, database::DatabaseManager, StatsClient, Result};
 use std::env;
 
 /// Test database configuration for integration tests
@@ -96,7 +96,7 @@ async fn test_data_validation() {
         .expect("Failed to validate data");
     
     assert!(report.is_valid, "Data validation should pass: {:?}", report.issues);
-    assert!(report.table_counts.len() > 0, "Should have table counts");
+    assert!(!report.table_counts.is_empty(), "Should have table counts");
     
     // Check that all expected tables have data
     let expected_tables = ["categories", "weapons", "barrels", "ammo_types", "weapon_ammo_stats", "configurations", "config_dropoffs"];
@@ -134,6 +134,87 @@ async fn test_database_reset() {
     assert_eq!(count_after.0, 0);
 }
 
+#[tokio::test]
+async fn test_schema_migration() {
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("migration").await.expect("Failed to setup test client");
+    let manager = client.database_manager();
+
+    // A database created before target profiles, attachments, hitbox multipliers,
+    // recoil and spread existed
+    let legacy_statements = [
+        "DROP TABLE target_profiles",
+        "DROP TABLE weapon_attachments",
+        "DROP TABLE attachments",
+        "ALTER TABLE weapon_ammo_stats DROP COLUMN upper_chest_multiplier",
+        "ALTER TABLE weapon_ammo_stats DROP COLUMN limb_multiplier",
+        "ALTER TABLE configurations DROP COLUMN recoil_vertical, DROP COLUMN recoil_horizontal, \
+         DROP COLUMN recoil_first_shot_multiplier, DROP COLUMN spread_ads, \
+         DROP COLUMN spread_hipfire, DROP COLUMN spread_bloom_per_shot",
+    ];
+    for statement in legacy_statements {
+        sqlx::query(statement)
+            .execute(manager.pool())
+            .await
+            .expect("Failed to downgrade schema");
+    }
+
+    // Migrating twice is harmless and leaves the weapon data alone
+    manager.migrate().await.expect("Failed to migrate database");
+    manager.migrate().await.expect("Failed to migrate database again");
+
+    let profiles: Vec<TargetProfile> = client
+        .target_profiles()
+        .try_collect()
+        .await
+        .expect("Failed to query target profiles");
+    assert_eq!(profiles, TargetProfile::presets());
+    assert!(manager.validate_data().await.expect("Failed to validate").is_valid);
+
+    // Restored multiplier columns are empty, so upper chest hits count as torso hits
+    let hitbox: Vec<_> = client
+        .hitbox_bullets_to_kill("M5A3", 30, &TargetProfile::soldier())
+        .try_collect()
+        .await
+        .expect("Failed to query hitbox bullets to kill");
+    assert!(!hitbox.is_empty());
+    assert!(hitbox.iter().all(|row| row.upper_chest == row.torso));
+
+    // Attachment tables are back, without any attachments for the embedded weapons
+    let attachments: Vec<_> = client
+        .weapon_attachments("M5A3")
+        .try_collect()
+        .await
+        .expect("Failed to query weapon attachments");
+    assert!(attachments.is_empty());
+
+    // Configurations load with empty recoil and spread
+    let configs: Vec<_> = client
+        .weapon_configs_grouped("M5A3")
+        .try_collect()
+        .await
+        .expect("Failed to query weapon configurations");
+    assert!(!configs.is_empty());
+    assert!(configs.iter().all(|config| config.recoil_vertical.is_none()));
+
+    // Profile tables created before the armor check gain it
+    sqlx::query(
+        "ALTER TABLE target_profiles DROP CONSTRAINT target_profiles_armor_damage_reduction_check",
+    )
+    .execute(manager.pool())
+    .await
+    .expect("Failed to drop armor check");
+    manager.migrate().await.expect("Failed to migrate database");
+    let inserted = sqlx::query(
+        "INSERT INTO target_profiles (profile_name, base_health, armor_damage_reduction) \
+         VALUES ('Impervious', 100, 1.00)",
+    )
+    .execute(manager.pool())
+    .await;
+    assert!(inserted.is_err(), "the migrated table should reject unstrippable armor");
+}
+
 #[tokio::test]
 async fn test_clear_data() {
     let manager = setup_test_db("clear").await.expect("Failed to setup test database");
@@ -193,4 +274,1628 @@ async fn test_stats_client_creation() {
     
     // Client should be successfully initialized
     // This test verifies that the client can be created and database is properly initialized
-}
\ No newline at end of file
+}
+
+/// Setup a populated test database and a client connected to it
+async fn setup_test_client(test_name: &str) -> Result<StatsClient> {
+    let manager = setup_test_db(test_name).await?;
+    manager.populate_from_embedded_data().await?;
+
+    StatsClient::new(&test_db_config(test_name)).await
+}
+
+#[tokio::test]
+async fn test_engagement_at_range() {
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("engagement").await.expect("Failed to setup test client");
+    let target = TargetProfile::default();
+
+    let rows: Vec<_> = client
+        .engagement_at_range("SWS-10", 100, 5.0, &target)
+        .try_collect()
+        .await
+        .expect("Failed to query engagement at range");
+
+    assert!(!rows.is_empty(), "SWS-10 should have configurations");
+    for row in &rows {
+        let flight_time = row.flight_time_ms.expect("Flight time should be set");
+        assert!(flight_time > 0.0);
+        assert!(row.lead_distance.unwrap() > 0.0);
+        assert!(row.bullet_drop.unwrap() > 0.0);
+        assert!(row.effective_ttk_ms.unwrap() >= flight_time);
+    }
+
+    // Plain damage queries leave the engagement columns empty
+    let plain: Vec<_> = client
+        .damage_at_range("SWS-10", 100)
+        .try_collect()
+        .await
+        .expect("Failed to query damage at range");
+    assert!(plain.iter().all(|row| row.flight_time_ms.is_none()));
+}
+
+#[tokio::test]
+async fn test_pareto_configs() {
+    use bf2042_stats::pareto::ParetoMetric;
+
+    let client = setup_test_client("pareto").await.expect("Failed to setup test client");
+    let target = TargetProfile::default();
+
+    let metrics = [
+        ParetoMetric::TtkAt(20),
+        ParetoMetric::TtkAt(50),
+        ParetoMetric::MagazineSize,
+        ParetoMetric::Velocity,
+        ParetoMetric::ReloadTime,
+    ];
+    let frontier = client
+        .pareto_configs("AK-24", &metrics, &target)
+        .await
+        .expect("Failed to compute pareto frontier");
+
+    assert!(!frontier.is_empty(), "Frontier should not be empty");
+    for metric in &metrics {
+        assert!(
+            frontier.iter().any(|config| config.wins.contains(metric)),
+            "Some frontier config should win on {}",
+            metric
+        );
+    }
+    for config in &frontier {
+        assert_eq!(config.values.len(), metrics.len());
+    }
+
+    // Unknown weapons and empty metric lists are rejected
+    assert!(client.pareto_configs("Not A Weapon", &metrics, &target).await.is_err());
+    assert!(client.pareto_configs("AK-24", &[], &target).await.is_err());
+}
+
+#[tokio::test]
+async fn test_range_band_winners() {
+    use bf2042_stats::models::{RangeBandQuery, RankBy};
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("range_bands").await.expect("Failed to setup test client");
+
+    let winners: Vec<_> = client
+        .range_band_winners(&RangeBandQuery::new(10, 100).with_limit(3))
+        .try_collect()
+        .await
+        .expect("Failed to query range band winners");
+
+    // 10 closed bands plus the open-ended 100m+ band, 3 winners each
+    assert_eq!(winners.len(), 33);
+    assert_eq!(winners[0].band_start, 0);
+    assert_eq!(winners[0].band_end, Some(10));
+    assert_eq!(winners.last().unwrap().band_start, 100);
+    assert_eq!(winners.last().unwrap().band_end, None);
+
+    for band in winners.chunks(3) {
+        assert!(band[0].ttk_ms <= band[1].ttk_ms && band[1].ttk_ms <= band[2].ttk_ms);
+    }
+
+    // Bands are scored inside their exclusive end, so a dropoff at 10m belongs to 10-20m
+    assert!(winners
+        .iter()
+        .all(|winner| winner.band_end.is_none_or(|end| winner.effective_range < end)));
+
+    // Every pellet counts, so a shotgun kills in one shot up close
+    assert!(winners[0].pellet_count.is_some());
+    assert_eq!(winners[0].bullets_to_kill, Some(1));
+
+    let by_damage: Vec<_> = client
+        .range_band_winners(&RangeBandQuery::new(25, 100).rank_by(RankBy::Damage))
+        .try_collect()
+        .await
+        .expect("Failed to query range band winners by damage");
+    assert_eq!(by_damage.len(), 5);
+    let shot_damage = |winner: &bf2042_stats::models::RangeBandWinner| {
+        winner.damage * rust_decimal::Decimal::from(winner.pellet_count.unwrap_or(1))
+    };
+    assert!(by_damage.windows(2).all(|pair| shot_damage(&pair[0]) >= shot_damage(&pair[1])));
+
+    // Unusable bands are rejected before querying
+    for (width, open_from) in [(0, 100), (10, -1)] {
+        let query = RangeBandQuery::new(width, open_from);
+        let winners = client.range_band_winners(&query);
+        assert!(winners.try_collect::<Vec<_>>().await.is_err());
+        assert!(client.range_band_winners_page(&query, 5, None).await.is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_compare_configs() {
+    use bf2042_stats::comparison::Side;
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("compare").await.expect("Failed to setup test client");
+    let target = TargetProfile::default();
+
+    let ak: Vec<_> = client.weapon_configs("AK-24").try_collect().await.expect("Failed to query AK-24");
+    let pbx: Vec<_> = client.weapon_configs("PBX-45").try_collect().await.expect("Failed to query PBX-45");
+
+    let ranges = [75, 0, 10, 20, 30, 40, 50, 60, 75, 100];
+    let comparison = client
+        .compare(ak[0].config_id, pbx[0].config_id, &ranges, &target)
+        .await
+        .expect("Failed to compare configurations");
+
+    assert_eq!(comparison.config_a.weapon_name, "AK-24");
+    assert_eq!(comparison.config_b.weapon_name, "PBX-45");
+
+    // Ranges are sorted and deduplicated
+    let compared: Vec<i16> = comparison.ranges.iter().map(|range| range.range).collect();
+    assert_eq!(compared, vec![0, 10, 20, 30, 40, 50, 60, 75, 100]);
+
+    for range in &comparison.ranges {
+        let (a, b) = (range.ttk_ms_a.unwrap(), range.ttk_ms_b.unwrap());
+        assert_eq!(range.advantage_ms, Some(b - a));
+    }
+    for crossover in &comparison.crossovers {
+        assert!(crossover.from_range < crossover.to_range);
+    }
+
+    // Buckshot kills in one shot up to 75m, then needs a second and loses to the rifle
+    let config_id = |configs: &[bf2042_stats::models::WeaponConfigWithDropoffs], barrel, ammo| {
+        configs
+            .iter()
+            .find(|c| c.barrel_name == barrel && c.ammo_type_name == ammo)
+            .unwrap()
+            .config_id
+    };
+    let shotgun: Vec<_> = client.weapon_configs("Super 500").try_collect().await.unwrap();
+    let comparison = client
+        .compare(
+            config_id(&shotgun, "Factory", "#01 Buckshot"),
+            config_id(&ak, "6KU", "Armor Piercing"),
+            &ranges,
+            &target,
+        )
+        .await
+        .expect("Failed to compare shotgun and rifle");
+    assert_eq!(comparison.ranges[0].winner, Some(Side::A));
+    assert_eq!(comparison.crossovers.len(), 1);
+    let crossover = &comparison.crossovers[0];
+    assert_eq!((crossover.from_range, crossover.to_range), (75, 100));
+    assert_eq!(crossover.winner, Side::B);
+
+    assert!(client.compare(ak[0].config_id, -1, &ranges, &target).await.is_err());
+}
+
+#[tokio::test]
+async fn test_expected_ttk_simulation() {
+    use bf2042_stats::simulation::AccuracyProfile;
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("simulation").await.expect("Failed to setup test client");
+    let target = TargetProfile::default();
+
+    let profile = AccuracyProfile::new(0.6, 0.2).with_trials(2_000).with_seed(42);
+    let first = client
+        .expected_ttk("M5A3", 30, &profile, &target)
+        .await
+        .expect("Failed to simulate expected TTK");
+    let second = client
+        .expected_ttk("M5A3", 30, &profile, &target)
+        .await
+        .expect("Failed to simulate expected TTK");
+
+    assert!(!first.is_empty());
+    for (a, b) in first.iter().zip(&second) {
+        // Same seed gives identical results
+        let (a, b) = (a.distribution.as_ref().unwrap(), b.distribution.as_ref().unwrap());
+        assert_eq!(a.mean_ttk_ms, b.mean_ttk_ms);
+        assert_eq!(a.p90_ttk_ms, b.p90_ttk_ms);
+        assert!(a.p50_ttk_ms <= a.p90_ttk_ms);
+    }
+
+    // Perfect torso accuracy reproduces the raw TTK
+    let perfect = client
+        .expected_ttk("M5A3", 30, &AccuracyProfile::new(1.0, 0.0).with_trials(10), &target)
+        .await
+        .expect("Failed to simulate perfect accuracy");
+    let raw: Vec<_> = client
+        .damage_at_range("M5A3", 30)
+        .try_collect()
+        .await
+        .expect("Failed to query damage at range");
+    for (simulated, row) in perfect.iter().zip(&raw) {
+        let rpm = bf2042_stats::ballistics::fire_rate(row.rpm_single, row.rpm_auto).unwrap();
+        let health = target.effective_health();
+        let btk =
+            bf2042_stats::ballistics::bullets_to_kill(row.damage, row.pellet_count, health).unwrap();
+        let distribution = simulated.distribution.as_ref().unwrap();
+        assert_eq!(distribution.kill_probability, 1.0);
+        assert_eq!(distribution.p50_ttk_ms, bf2042_stats::ballistics::time_to_kill_ms(btk, rpm));
+    }
+
+    // Configurations without ammo stats are kept, without a simulation
+    sqlx::query(
+        "DELETE FROM weapon_ammo_stats was USING weapons w \
+         WHERE was.weapon_id = w.weapon_id AND w.weapon_name = 'M5A3'",
+    )
+    .execute(client.database_manager().pool())
+    .await
+    .expect("Failed to delete ammo stats");
+    let unsimulated = client
+        .expected_ttk("M5A3", 30, &profile, &target)
+        .await
+        .expect("Failed to simulate without ammo stats");
+    assert_eq!(unsimulated.len(), first.len());
+    assert!(unsimulated
+        .iter()
+        .all(|ttk| ttk.distribution.is_none() && ttk.headshot_multiplier.is_none()));
+
+    assert!(client.expected_ttk("M5A3", 30, &AccuracyProfile::new(0.0, 0.5), &target).await.is_err());
+}
+
+#[tokio::test]
+async fn test_shotgun_pellets() {
+    use bf2042_stats::pareto::ParetoMetric;
+    use bf2042_stats::simulation::AccuracyProfile;
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("shotgun").await.expect("Failed to setup test client");
+    let target = TargetProfile::default();
+
+    // Every consumer counts all pellets of a shot
+    let rows: Vec<_> =
+        client.hitbox_bullets_to_kill("Super 500", 0, &target).try_collect().await.unwrap();
+    let buckshot = rows.iter().find(|row| row.ammo_type_name == "#01 Buckshot").unwrap();
+    assert_eq!(buckshot.torso, Some(1));
+
+    let rows: Vec<_> =
+        client.engagement_at_range("Super 500", 0, 0.0, &target).try_collect().await.unwrap();
+    let buckshot = rows.iter().find(|row| row.ammo_type_name == "#01 Buckshot").unwrap();
+    assert_eq!(buckshot.pellet_count, Some(11));
+    assert_eq!(buckshot.effective_ttk_ms, Some(0.0));
+    let slug = rows.iter().find(|row| row.ammo_type_name == "Slug").unwrap();
+    assert_eq!(slug.pellet_count, None);
+    assert_eq!(slug.effective_ttk_ms, Some(800.0));
+
+    let details = client.weapon_details_aggregated("Super 500").await.unwrap();
+    assert_eq!(details.metrics.best_ttk_ms, Some(0.0));
+
+    let frontier =
+        client.pareto_configs("Super 500", &[ParetoMetric::TtkAt(0)], &target).await.unwrap();
+    assert!(frontier.iter().all(|config| config.values[0] == Some(0.0)));
+
+    let config_id = |ammo: &str| {
+        let config = details.configurations.iter().find(|config| config.ammo_type_name == ammo);
+        config.unwrap().config_id
+    };
+    let comparison =
+        client.compare(config_id("#01 Buckshot"), config_id("Slug"), &[0], &target).await.unwrap();
+    assert_eq!(comparison.ranges[0].bullets_to_kill_a, Some(1));
+    assert_eq!(comparison.ranges[0].bullets_to_kill_b, Some(2));
+
+    let simulated = client
+        .expected_ttk("Super 500", 0, &AccuracyProfile::new(1.0, 0.0).with_trials(10), &target)
+        .await
+        .unwrap();
+    let buckshot = simulated.iter().find(|ttk| ttk.ammo_type_name == "#01 Buckshot").unwrap();
+    assert_eq!(buckshot.distribution.as_ref().unwrap().mean_shots, Some(1.0));
+}
+
+#[tokio::test]
+async fn test_target_profiles() {
+    use bf2042_stats::models::{Hitbox, HitboxMultipliers};
+    use futures::TryStreamExt;
+    use rust_decimal::Decimal;
+
+    let client = setup_test_client("target_profiles").await.expect("Failed to setup test client");
+
+    // Presets are stored on population
+    let profiles: Vec<TargetProfile> = client
+        .target_profiles()
+        .try_collect()
+        .await
+        .expect("Failed to query target profiles");
+    assert_eq!(profiles, TargetProfile::presets());
+
+    // Armor with 50% reduction costs twice its points in raw damage
+    let plated = TargetProfile {
+        profile_name: "Plated".to_string(),
+        armor_health: 50,
+        armor_damage_reduction: Decimal::new(50, 2),
+        headshot_multiplier_override: Some(Decimal::ONE),
+        ..TargetProfile::soldier()
+    };
+    assert_eq!(plated.effective_health(), Decimal::new(200, 0));
+    let ammo = HitboxMultipliers { headshot: Decimal::new(20, 1), ..Default::default() };
+    assert_eq!(plated.hit_multiplier(Hitbox::Head, &ammo), Decimal::ONE);
+
+    client
+        .database_manager()
+        .save_target_profile(&plated)
+        .await
+        .expect("Failed to save target profile");
+    let stored = client
+        .target_profile("Plated")
+        .await
+        .expect("Failed to query target profile");
+    assert_eq!(stored, plated);
+    assert!(client.target_profile("Missing").await.is_err());
+
+    // Armor that absorbs every hit can never be stripped
+    let impervious = TargetProfile {
+        profile_name: "Impervious".to_string(),
+        armor_damage_reduction: Decimal::ONE,
+        ..plated.clone()
+    };
+    let err = client.database_manager().save_target_profile(&impervious).await.unwrap_err();
+    assert!(err.to_string().contains("Armor damage reduction must be in [0, 1)"), "{}", err);
+    let inserted = sqlx::query(
+        "INSERT INTO target_profiles (profile_name, base_health, armor_damage_reduction) \
+         VALUES ('Impervious', 100, 1.00)",
+    )
+    .execute(client.database_manager().pool())
+    .await;
+    assert!(inserted.is_err(), "the table should reject unstrippable armor");
+    assert!(client.target_profile("Impervious").await.is_err());
+
+    // Armored targets take at least as many hits at every range
+    let soldier = client
+        .compare(1, 1, &[0, 50, 100], &TargetProfile::soldier())
+        .await
+        .expect("Failed to compare against soldier");
+    let armored = client
+        .compare(1, 1, &[0, 50, 100], &stored)
+        .await
+        .expect("Failed to compare against armored target");
+    for (soft, hard) in soldier.ranges.iter().zip(&armored.ranges) {
+        assert!(hard.bullets_to_kill_a > soft.bullets_to_kill_a);
+    }
+}
+
+#[tokio::test]
+async fn test_hitbox_bullets_to_kill() {
+    use bf2042_stats::simulation::AccuracyProfile;
+    use futures::TryStreamExt;
+    use rust_decimal::Decimal;
+
+    let client = setup_test_client("hitbox_btk").await.expect("Failed to setup test client");
+
+    let target = TargetProfile {
+        limb_multiplier: Decimal::new(50, 2),
+        ..TargetProfile::soldier()
+    };
+    let rows: Vec<_> = client
+        .hitbox_bullets_to_kill("M5A3", 30, &target)
+        .try_collect()
+        .await
+        .expect("Failed to query hitbox bullets to kill");
+    assert!(!rows.is_empty(), "Should have hitbox rows for M5A3");
+
+    for row in &rows {
+        let torso = row.torso.expect("Torso hits should kill");
+        assert!(row.head.unwrap() <= torso);
+        assert!(row.limb.unwrap() >= torso);
+    }
+
+    // Limb-only hits on a half-damage limb target take longer than torso hits
+    let torso_only = client
+        .expected_ttk("M5A3", 30, &AccuracyProfile::new(1.0, 0.0).with_trials(100), &target)
+        .await
+        .expect("Failed to simulate torso hits");
+    let limb_only = client
+        .expected_ttk(
+            "M5A3",
+            30,
+            &AccuracyProfile::new(1.0, 0.0).with_hit_locations(0.0, 1.0).with_trials(100),
+            &target,
+        )
+        .await
+        .expect("Failed to simulate limb hits");
+    for (torso, limb) in torso_only.iter().zip(&limb_only) {
+        let (torso, limb) = (torso.distribution.as_ref(), limb.distribution.as_ref());
+        assert!(limb.unwrap().p50_ttk_ms >= torso.unwrap().p50_ttk_ms);
+    }
+}
+
+#[tokio::test]
+async fn test_loadouts() {
+    use bf2042_stats::models::{AttachmentData, WeaponsData};
+    use futures::TryStreamExt;
+    use rust_decimal::Decimal;
+
+    let mut data: WeaponsData = serde_json::from_str(include_str!("../weapons.json"))
+        .expect("Failed to parse weapons.json");
+    let attachment = |name: &str, slot: &str, velocity: Option<Decimal>, rpm: Option<Decimal>| AttachmentData {
+        name: name.to_string(),
+        slot: slot.to_string(),
+        weapons: vec!["M5A3".to_string()],
+        velocity_modifier: velocity,
+        rpm_modifier: rpm,
+        spread_modifier: None,
+    };
+    data.attachments = vec![
+        attachment("Compensator", "Muzzle", None, None),
+        attachment("Extended Barrel", "Muzzle", Some(Decimal::new(110, 2)), None),
+        attachment("Red Dot", "Optic", None, None),
+        attachment("Bipod", "Underbarrel", None, Some(Decimal::new(90, 2))),
+    ];
+
+    let manager = setup_test_db("loadouts").await.expect("Failed to setup test database");
+    manager
+        .populate_from_json_str(&serde_json::to_string(&data).unwrap())
+        .await
+        .expect("Failed to populate database with attachments");
+    let client = StatsClient::new(&test_db_config("loadouts"))
+        .await
+        .expect("Failed to create client");
+
+    let mut config_ids: Vec<i32> = client
+        .weapon_configs("M5A3")
+        .map_ok(|row| row.config_id)
+        .try_collect()
+        .await
+        .expect("Failed to query configurations");
+    config_ids.dedup();
+
+    let loadouts: Vec<_> = client
+        .loadouts("M5A3")
+        .try_collect()
+        .await
+        .expect("Failed to query loadouts");
+    assert_eq!(loadouts.len(), config_ids.len() * 2);
+
+    // Muzzle is the only slot with a choice, so loadouts come in pairs per configuration
+    for pair in loadouts.chunks(2) {
+        let (compensated, extended) = (&pair[0], &pair[1]);
+        assert_eq!(compensated.config_id, extended.config_id);
+        let slots: Vec<&str> = compensated.attachments.iter().map(|a| a.slot.as_str()).collect();
+        assert_eq!(slots, ["Muzzle", "Optic", "Underbarrel"]);
+        assert_eq!(extended.attachments[0].attachment_name, "Extended Barrel");
+        assert!(extended.velocity > compensated.velocity);
+        assert_eq!(extended.rpm_auto, compensated.rpm_auto);
+    }
+
+    // Weapons without attachments yield their bare configurations
+    let bare: Vec<_> = client
+        .loadouts("G57")
+        .try_collect()
+        .await
+        .expect("Failed to query bare loadouts");
+    assert!(!bare.is_empty());
+    assert!(bare.iter().all(|loadout| loadout.attachments.is_empty()));
+}
+
+#[tokio::test]
+async fn test_recoil_and_spread_stats() {
+    use bf2042_stats::models::WeaponsData;
+    use futures::TryStreamExt;
+    use rust_decimal::Decimal;
+
+    let mut data: WeaponsData = serde_json::from_str(include_str!("../weapons.json"))
+        .expect("Failed to parse weapons.json");
+    for weapon in data.categories.iter_mut().flat_map(|category| category.weapons.iter_mut()) {
+        if weapon.name == "M5A3" {
+            for stat in &mut weapon.stats {
+                stat.recoil_vertical = Some(Decimal::new(450, 3));
+                stat.recoil_horizontal = Some(Decimal::new(150, 3));
+                stat.recoil_first_shot_multiplier = Some(Decimal::new(150, 2));
+                stat.spread_ads = Some(Decimal::new(50, 3));
+                stat.spread_hipfire = Some(Decimal::new(2500, 3));
+                stat.spread_bloom_per_shot = Some(Decimal::new(125, 3));
+            }
+        }
+    }
+
+    let manager = setup_test_db("recoil").await.expect("Failed to setup test database");
+    manager
+        .populate_from_json_str(&serde_json::to_string(&data).unwrap())
+        .await
+        .expect("Failed to populate database with handling stats");
+    let client = StatsClient::new(&test_db_config("recoil"))
+        .await
+        .expect("Failed to create client");
+
+    let configs: Vec<_> = client
+        .weapon_configs("M5A3")
+        .try_collect()
+        .await
+        .expect("Failed to query weapon configs");
+    assert!(!configs.is_empty());
+    for config in &configs {
+        assert_eq!(config.recoil_vertical, Some(Decimal::new(450, 3)));
+        assert_eq!(config.recoil_first_shot_multiplier, Some(Decimal::new(150, 2)));
+        assert_eq!(config.spread_bloom_per_shot, Some(Decimal::new(125, 3)));
+    }
+
+    // Weapons without handling data keep their stats empty
+    let best: Vec<_> = client
+        .best_configs_in_category("Assault Rifles", 30, 100)
+        .try_collect()
+        .await
+        .expect("Failed to query best configs");
+    for config in &best {
+        let expected = (config.weapon_name == "M5A3").then(|| Decimal::new(2500, 3));
+        assert_eq!(config.spread_hipfire, expected);
+    }
+    assert!(best.iter().any(|config| config.weapon_name == "M5A3"));
+}
+
+#[tokio::test]
+async fn test_weapon_configs_grouped() {
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("configs_grouped").await.expect("Failed to setup test client");
+
+    let rows: Vec<_> = client
+        .weapon_configs("M5A3")
+        .try_collect()
+        .await
+        .expect("Failed to query weapon configs");
+    let configs: Vec<_> = client
+        .weapon_configs_grouped("M5A3")
+        .try_collect()
+        .await
+        .expect("Failed to query grouped weapon configs");
+
+    let mut config_ids: Vec<i32> = rows.iter().map(|row| row.config_id).collect();
+    config_ids.dedup();
+    assert_eq!(configs.iter().map(|config| config.config_id).collect::<Vec<_>>(), config_ids);
+    assert_eq!(configs.iter().map(|config| config.dropoffs.len()).sum::<usize>(), rows.len());
+
+    for config in &configs {
+        assert!(config.dropoffs.windows(2).all(|pair| pair[0].range < pair[1].range));
+        let ammo = config.ammo_stats.as_ref().expect("Ammo stats should be attached");
+        assert_eq!(ammo.ammo_type_name, config.ammo_type_name);
+    }
+
+    let missing: Vec<_> = client
+        .weapon_configs_grouped("Missing")
+        .try_collect()
+        .await
+        .expect("Failed to query missing weapon");
+    assert!(missing.is_empty());
+}
+
+#[tokio::test]
+async fn test_weapon_details_aggregated() {
+    let client = setup_test_client("details_aggregated").await.expect("Failed to setup test client");
+
+    let details = client
+        .weapon_details_aggregated("M5A3")
+        .await
+        .expect("Failed to query weapon details");
+    assert_eq!(details.weapon.weapon_name, "M5A3");
+    assert_eq!(details.category_name, "Assault Rifles");
+    assert!(!details.configurations.is_empty());
+    assert!(!details.ammo_stats.is_empty());
+    assert_eq!(details.metrics.configuration_count, details.configurations.len());
+    assert!(details.metrics.max_damage >= details.metrics.min_damage);
+    assert!(details.metrics.best_ttk_ms.is_some());
+
+    // The aggregate serializes in one piece
+    let json = serde_json::to_value(&details).expect("Failed to serialize weapon details");
+    assert_eq!(json["category_name"], "Assault Rifles");
+
+    assert!(client.weapon_details_aggregated("Missing").await.is_err());
+}
+
+#[tokio::test]
+async fn test_config_query() {
+    use bf2042_stats::models::{ConfigQuery, ConfigSort, SortOrder};
+    use futures::TryStreamExt;
+    use rust_decimal::Decimal;
+
+    let client = setup_test_client("config_query").await.expect("Failed to setup test client");
+
+    let query = ConfigQuery::new()
+        .with_category("Assault Rifles")
+        .with_barrel("Factory")
+        .with_velocity(Some(701), None)
+        .with_magazine(Some(30), None)
+        .at_range(40)
+        .with_damage(Some(Decimal::new(25, 0)), None)
+        .sort_by(ConfigSort::Velocity, SortOrder::Desc);
+    let configs: Vec<_> = client
+        .query_configs(&query)
+        .try_collect()
+        .await
+        .expect("Failed to run configuration query");
+    assert!(!configs.is_empty(), "Should match some assault rifle configurations");
+
+    for config in &configs {
+        assert_eq!(config.category_name, "Assault Rifles");
+        assert_eq!(config.barrel_name, "Factory");
+        assert!(config.velocity > 700);
+        assert!(config.magazine_size >= 30);
+        assert!(config.effective_range <= 40);
+        assert!(config.damage >= Decimal::new(25, 0));
+    }
+    assert!(configs.windows(2).all(|pair| pair[0].velocity >= pair[1].velocity));
+
+    // Limit and offset window the same ordering
+    let page: Vec<_> = client
+        .query_configs(&query.clone().with_limit(2).with_offset(1))
+        .try_collect()
+        .await
+        .expect("Failed to run windowed configuration query");
+    let expected: Vec<i32> = configs.iter().skip(1).take(2).map(|c| c.config_id).collect();
+    assert_eq!(page.iter().map(|c| c.config_id).collect::<Vec<_>>(), expected);
+
+    // Configurations whose ammo has no stats are left out instead of failing the query
+    let missing = &configs[0];
+    sqlx::query(
+        "DELETE FROM weapon_ammo_stats was USING weapons w, ammo_types a \
+         WHERE was.weapon_id = w.weapon_id AND was.ammo_id = a.ammo_id \
+           AND w.weapon_name = $1 AND a.ammo_type_name = $2",
+    )
+    .bind(&missing.weapon_name)
+    .bind(&missing.ammo_type_name)
+    .execute(client.database_manager().pool())
+    .await
+    .expect("Failed to delete ammo stats");
+    let remaining: Vec<_> = client
+        .query_configs(&ConfigQuery::new().with_weapon(&missing.weapon_name))
+        .try_collect()
+        .await
+        .expect("Failed to query configurations without ammo stats");
+    assert!(!remaining.is_empty());
+    assert!(remaining.iter().all(|c| c.ammo_type_name != missing.ammo_type_name));
+}
+
+#[tokio::test]
+async fn test_keyset_pagination() {
+    use bf2042_stats::models::{ConfigQuery, ConfigSort, Cursor, RangeBandQuery, SortOrder};
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("pagination").await.expect("Failed to setup test client");
+
+    // Walk every page of a query and return the concatenated items
+    macro_rules! walk {
+        ($limit:expr, |$cursor:ident| $page:expr) => {{
+            let mut items = Vec::new();
+            let mut next: Option<Cursor> = None;
+            loop {
+                let $cursor = next.as_ref();
+                let page = $page.await.expect("Failed to query page");
+                assert!(page.items.len() <= $limit);
+                items.extend(page.items);
+                match page.next_cursor {
+                    Some(cursor) => next = Some(cursor),
+                    None => break,
+                }
+            }
+            items
+        }};
+    }
+
+    let all: Vec<_> = client.weapons_by_category("Assault Rifles").try_collect().await.unwrap();
+    let paged = walk!(3, |cursor| client.weapons_by_category_page("Assault Rifles", 3, cursor));
+    assert_eq!(
+        paged.iter().map(|w| &w.weapon_name).collect::<Vec<_>>(),
+        all.iter().map(|w| &w.weapon_name).collect::<Vec<_>>()
+    );
+
+    let all: Vec<_> = client.categories().try_collect().await.unwrap();
+    let paged = walk!(2, |cursor| client.categories_page(2, cursor));
+    assert_eq!(
+        paged.iter().map(|c| &c.category_name).collect::<Vec<_>>(),
+        all.iter().map(|c| &c.category_name).collect::<Vec<_>>()
+    );
+
+    let all: Vec<_> = client.barrels().try_collect().await.unwrap();
+    let paged = walk!(5, |cursor| client.barrels_page(5, cursor));
+    assert_eq!(
+        paged.iter().map(|b| &b.barrel_name).collect::<Vec<_>>(),
+        all.iter().map(|b| &b.barrel_name).collect::<Vec<_>>()
+    );
+
+    let all: Vec<_> = client.ammo_types().try_collect().await.unwrap();
+    let paged = walk!(5, |cursor| client.ammo_types_page(5, cursor));
+    assert_eq!(
+        paged.iter().map(|a| &a.ammo_type_name).collect::<Vec<_>>(),
+        all.iter().map(|a| &a.ammo_type_name).collect::<Vec<_>>()
+    );
+
+    // Weapons are ordered by category first, so the key spans both columns
+    let all: Vec<_> = client.all_weapons().try_collect().await.unwrap();
+    let paged = walk!(6, |cursor| client.all_weapons_page(6, cursor));
+    assert_eq!(
+        paged.iter().map(|w| (&w.category_name, &w.weapon_name)).collect::<Vec<_>>(),
+        all.iter().map(|w| (&w.category_name, &w.weapon_name)).collect::<Vec<_>>()
+    );
+
+    let all: Vec<_> = client.weapon_configs("M5A3").try_collect().await.unwrap();
+    let paged = walk!(7, |cursor| client.weapon_configs_page("M5A3", 7, cursor));
+    assert_eq!(
+        paged.iter().map(|r| (r.config_id, r.range)).collect::<Vec<_>>(),
+        all.iter().map(|r| (r.config_id, r.range)).collect::<Vec<_>>()
+    );
+
+    let all: Vec<_> = client.weapon_ammo_stats("M5A3").try_collect().await.unwrap();
+    let paged = walk!(1, |cursor| client.weapon_ammo_stats_page("M5A3", 1, cursor));
+    assert_eq!(paged.len(), all.len());
+
+    let all: Vec<_> = client.damage_at_range("M5A3", 40).try_collect().await.unwrap();
+    let paged = walk!(4, |cursor| client.damage_at_range_page("M5A3", 40, 4, cursor));
+    assert_eq!(
+        paged.iter().map(|r| (&r.barrel_name, &r.ammo_type_name)).collect::<Vec<_>>(),
+        all.iter().map(|r| (&r.barrel_name, &r.ammo_type_name)).collect::<Vec<_>>()
+    );
+
+    // Sort keys with ties and missing values page in the same order as the stream
+    for sort in [ConfigSort::Damage, ConfigSort::ReloadTime, ConfigSort::FireRate] {
+        let query = ConfigQuery::new().with_category("Assault Rifles").at_range(40).sort_by(sort, SortOrder::Desc);
+        let all: Vec<_> = client.query_configs(&query).try_collect().await.unwrap();
+        let paged = walk!(10, |cursor| client.query_configs_page(&query, 10, cursor));
+        assert_eq!(
+            paged.iter().map(|c| c.config_id).collect::<Vec<_>>(),
+            all.iter().map(|c| c.config_id).collect::<Vec<_>>()
+        );
+    }
+
+    let query = RangeBandQuery::new(25, 100).with_limit(3);
+    let all: Vec<_> = client
+        .range_band_winners(&query)
+        .try_collect()
+        .await
+        .unwrap();
+    let paged = walk!(4, |cursor| client.range_band_winners_page(&query, 4, cursor));
+    assert_eq!(
+        paged.iter().map(|w| (w.band_start, w.rank)).collect::<Vec<_>>(),
+        all.iter().map(|w| (w.band_start, w.rank)).collect::<Vec<_>>()
+    );
+
+    assert!(client.weapons_by_category_page("Assault Rifles", 0, None).await.is_err());
+    let bogus = Cursor::from("not a cursor".to_string());
+    assert!(client.weapons_by_category_page("Assault Rifles", 3, Some(&bogus)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_listing_queries() {
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("listings").await.expect("Failed to setup test client");
+
+    let categories: Vec<_> = client.categories().try_collect().await.expect("Failed to list categories");
+    let weapons: Vec<_> = client.all_weapons().try_collect().await.expect("Failed to list weapons");
+    assert!(categories.iter().any(|c| c.category_name == "Assault Rifles"));
+    assert_eq!(categories.iter().map(|c| c.weapon_count).sum::<i64>(), weapons.len() as i64);
+
+    let barrels: Vec<_> = client.barrels().try_collect().await.expect("Failed to list barrels");
+    let ammo_types: Vec<_> = client.ammo_types().try_collect().await.expect("Failed to list ammo types");
+    let config_count: i64 = weapons.iter().map(|w| w.config_count).sum();
+    assert!(config_count > 0);
+    assert_eq!(barrels.iter().map(|b| b.config_count).sum::<i64>(), config_count);
+    assert_eq!(ammo_types.iter().map(|a| a.config_count).sum::<i64>(), config_count);
+
+    let m5a3 = weapons.iter().find(|w| w.weapon_name == "M5A3").expect("M5A3 should be listed");
+    assert_eq!(m5a3.category_name, "Assault Rifles");
+}
+
+#[tokio::test]
+async fn test_reverse_lookups() {
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("reverse_lookups").await.expect("Failed to setup test client");
+
+    let with_barrel: Vec<_> = client
+        .weapons_with_barrel("6KU")
+        .try_collect()
+        .await
+        .expect("Failed to query weapons with barrel");
+    assert!(!with_barrel.is_empty());
+    assert!(with_barrel.windows(2).all(|pair| pair[0].weapon_name < pair[1].weapon_name));
+    for weapon in &with_barrel {
+        assert!(weapon.configurations.iter().all(|c| c.barrel_name == "6KU" && c.weapon_name == weapon.weapon_name));
+    }
+
+    let with_ammo: Vec<_> = client
+        .weapons_with_ammo("Subsonic")
+        .try_collect()
+        .await
+        .expect("Failed to query weapons with ammo");
+    assert!(with_ammo.iter().any(|weapon| weapon.weapon_name == "M5A3"));
+    for weapon in &with_ammo {
+        assert!(weapon.configurations.iter().all(|c| c.ammo_type_name == "Subsonic"));
+    }
+
+    let comparisons: Vec<_> = client
+        .ammo_comparison("High Power", "Subsonic")
+        .try_collect()
+        .await
+        .expect("Failed to compare ammo types");
+    assert!(!comparisons.is_empty());
+    for comparison in &comparisons {
+        assert_eq!((comparison.ammo_a.as_str(), comparison.ammo_b.as_str()), ("High Power", "Subsonic"));
+        assert!(with_ammo.iter().any(|weapon| weapon.weapon_name == comparison.weapon_name));
+        assert!(comparison.dropoffs.windows(2).all(|pair| pair[0].range < pair[1].range));
+        let expected = f64::from(comparison.velocity_b - comparison.velocity_a) * 100.0
+            / f64::from(comparison.velocity_a);
+        assert_eq!(comparison.velocity_change_pct, Some(expected));
+    }
+}
+
+#[tokio::test]
+async fn test_attachment_effects() {
+    use bf2042_stats::effects::Modification;
+
+    let client = setup_test_client("attachment_effects").await.expect("Failed to setup test client");
+    let target = TargetProfile::default();
+
+    let deltas = client
+        .attachment_deltas("M5A3", 25, 100, &target)
+        .await
+        .expect("Failed to compute attachment deltas");
+    assert!(!deltas.is_empty());
+    for delta in &deltas {
+        assert_eq!(delta.bands.len(), 5);
+        match delta.modification {
+            Modification::Barrel => assert_ne!(delta.name, "Factory"),
+            Modification::Ammo => assert_ne!(delta.name, "Standard"),
+        }
+    }
+
+    let effects = client
+        .attachment_effects(25, 100, &target)
+        .await
+        .expect("Failed to compute attachment effects");
+    let six_ku = effects
+        .iter()
+        .find(|effect| effect.modification == Modification::Barrel && effect.name == "6KU")
+        .expect("6KU barrel should have an effect");
+    assert!(six_ku.weapon_count > 1);
+    assert!(six_ku.sample_count >= six_ku.weapon_count);
+    assert!(six_ku.mean_velocity_change_pct.is_some());
+
+    // The M5A3 deltas are part of the arsenal-wide 6KU average
+    let m5a3_6ku = deltas
+        .iter()
+        .filter(|delta| delta.modification == Modification::Barrel && delta.name == "6KU")
+        .count();
+    assert!(six_ku.sample_count >= m5a3_6ku);
+
+    assert!(client.attachment_effects(0, 100, &target).await.is_err());
+    assert!(client.attachment_deltas("Missing", 25, 100, &target).await.is_err());
+}
+
+#[cfg(feature = "http")]
+#[tokio::test]
+async fn test_http_api() {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    let client = setup_test_client("http").await.expect("Failed to setup test client");
+    let app = bf2042_stats::http::router(Arc::new(client));
+
+    let get = |uri: &str| {
+        let request = Request::get(uri).body(Body::empty()).unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(request).await.unwrap();
+            let status = response.status();
+            let content_type = response.headers()["content-type"].to_str().unwrap().to_string();
+            let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, content_type, String::from_utf8(body.to_vec()).unwrap())
+        }
+    };
+    let lines = |body: &str| -> Vec<serde_json::Value> {
+        body.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    };
+
+    let (status, content_type, body) = get("/categories").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/x-ndjson");
+    assert!(lines(&body).iter().any(|c| c["category_name"] == "Assault Rifles"));
+
+    let (status, content_type, body) = get("/openapi.json").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/json");
+    assert!(serde_json::from_str::<serde_json::Value>(&body).unwrap()["paths"]["/weapons/{name}"].is_object());
+
+    let (status, _, body) = get("/weapons/M5A3").await;
+    assert_eq!(status, StatusCode::OK);
+    let details: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(details["category_name"], "Assault Rifles");
+
+    let (status, _, body) = get("/weapons/M5A3/configs").await;
+    assert_eq!(status, StatusCode::OK);
+    let configs = lines(&body);
+    assert_eq!(configs.len(), details["configurations"].as_array().unwrap().len());
+
+    let (status, _, body) = get("/weapons/M5A3/damage?range=30").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!lines(&body).is_empty());
+
+    let (status, _, body) = get("/categories/Assault%20Rifles/best?range=50&limit=3").await;
+    assert_eq!(status, StatusCode::OK);
+    let best = lines(&body);
+    assert_eq!(best.len(), 3);
+    assert!(best.iter().all(|c| c["category_name"] == "Assault Rifles"));
+
+    for uri in ["/weapons/Missing", "/weapons/Missing/configs", "/weapons/Missing/damage", "/categories/Missing/best"] {
+        let (status, content_type, body) = get(uri).await;
+        assert_eq!(status, StatusCode::NOT_FOUND, "{}", uri);
+        assert_eq!(content_type, "application/json");
+        assert!(body.contains("not found"));
+    }
+
+    let (status, _, _) = get("/weapons/M5A3/damage?range=far").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_openapi_document() {
+    use utoipa::OpenApi;
+
+    let doc = serde_json::to_value(bf2042_stats::http::ApiDoc::openapi()).unwrap();
+    assert!(doc["openapi"].as_str().unwrap().starts_with("3."));
+    for path in ["/categories", "/categories/{name}/best", "/weapons/{name}", "/weapons/{name}/configs", "/weapons/{name}/damage"] {
+        assert!(doc["paths"][path]["get"].is_object(), "{}", path);
+    }
+    let best = &doc["paths"]["/categories/{name}/best"]["get"]["responses"];
+    assert_eq!(best["200"]["content"]["application/x-ndjson"]["schema"]["$ref"], "#/components/schemas/BestConfigInCategory");
+    assert_eq!(best["404"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/ErrorBody");
+
+    let schemas = &doc["components"]["schemas"];
+    for (name, sql) in [("Damage", "DECIMAL(5,1)"), ("ReloadTime", "DECIMAL(4,2)"), ("HeadshotMultiplier", "DECIMAL(3,1)")] {
+        assert_eq!(schemas[name]["type"], "string");
+        assert_eq!(schemas[name]["format"], "decimal");
+        assert!(schemas[name]["description"].as_str().unwrap().starts_with(sql));
+    }
+    assert_eq!(schemas["Damage"]["pattern"], r"^-?\d{1,4}(\.\d)?$");
+
+    let best_config = &schemas["BestConfigInCategory"]["properties"];
+    assert_eq!(best_config["damage"]["$ref"], "#/components/schemas/Damage");
+    assert_eq!(best_config["headshot_multiplier"]["$ref"], "#/components/schemas/HeadshotMultiplier");
+    assert!(best_config["tactical_reload_time"].to_string().contains("#/components/schemas/ReloadTime"));
+    assert!(schemas["WeaponConfigWithDropoffs"].is_object());
+}
+
+#[cfg(feature = "graphql")]
+#[tokio::test]
+async fn test_graphql_nested_queries() {
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+
+    let client = Arc::new(setup_test_client("graphql").await.expect("Failed to setup test client"));
+    let schema = bf2042_stats::graphql::schema(client.clone());
+
+    let response = schema
+        .execute(
+            r#"{
+                category(name: "Assault Rifles") {
+                    name
+                    weapons {
+                        name
+                        category { name }
+                        ammoStats { ammoType { name } magazineSize headshotMultiplier }
+                        configurations {
+                            id
+                            barrel { name }
+                            ammoType { name }
+                            dropoffs { range damage }
+                            damageAt(range: 30)
+                        }
+                    }
+                }
+                missing: weapon(name: "Missing") { name }
+            }"#,
+        )
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert!(data["missing"].is_null());
+
+    let weapons = data["category"]["weapons"].as_array().unwrap();
+    assert!(weapons.iter().all(|w| w["category"]["name"] == "Assault Rifles"));
+    let m5a3 = weapons.iter().find(|w| w["name"] == "M5A3").expect("M5A3 should be nested");
+    assert!(!m5a3["ammoStats"].as_array().unwrap().is_empty());
+
+    let configs: Vec<_> = client.weapon_configs_grouped("M5A3").try_collect().await.unwrap();
+    let nested = m5a3["configurations"].as_array().unwrap();
+    assert_eq!(nested.len(), configs.len());
+    for config in &configs {
+        let node = nested.iter().find(|c| c["id"] == config.config_id).unwrap();
+        assert_eq!(node["barrel"]["name"], config.barrel_name.as_str());
+        assert_eq!(node["ammoType"]["name"], config.ammo_type_name.as_str());
+        assert_eq!(node["dropoffs"].as_array().unwrap().len(), config.dropoffs.len());
+        assert_eq!(node["damageAt"], serde_json::to_value(config.damage_at(30)).unwrap());
+    }
+
+    let response = schema
+        .execute(r#"{ ranking(filter: {category: "Assault Rifles"}, range: 50, limit: 3) { rank damage configuration { id weapon { name } } } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let ranking = response.data.into_json().unwrap()["ranking"].as_array().unwrap().clone();
+    let best: Vec<_> = client.best_configs_in_category("Assault Rifles", 50, 3).try_collect().await.unwrap();
+    assert_eq!(ranking.len(), best.len());
+    for (idx, (entry, row)) in ranking.iter().zip(&best).enumerate() {
+        assert_eq!(entry["rank"], idx + 1);
+        assert_eq!(entry["configuration"]["id"], row.config_id);
+        assert_eq!(entry["configuration"]["weapon"]["name"], row.weapon_name.as_str());
+    }
+
+    let response = schema
+        .execute(r#"{ ranking(sortBy: FIRE_RATE, order: ASC, limit: 1) { rank } }"#)
+        .await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    let request = Request::post("/graphql")
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"query": "{ weapon(name: \"M5A3\") { category { name } } }"}"#))
+        .unwrap();
+    let response = bf2042_stats::http::router(client).oneshot(request).await.unwrap();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["data"]["weapon"]["category"]["name"], "Assault Rifles");
+}
+
+#[cfg(feature = "grpc")]
+#[tokio::test]
+async fn test_grpc_service() {
+    use bf2042_stats::grpc::{self, GrpcStatsClient};
+    use bf2042_stats::pareto::ParetoMetric;
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+    use tonic::transport::server::TcpIncoming;
+
+    let client = Arc::new(setup_test_client("grpc").await.expect("Failed to setup test client"));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(grpc::server(client.clone()))
+            .serve_with_incoming(TcpIncoming::from(listener)),
+    );
+    let remote = GrpcStatsClient::connect(format!("http://{}", addr)).await.unwrap();
+
+    let local: Vec<_> = client.categories().try_collect().await.unwrap();
+    let streamed: Vec<_> = remote.categories().await.unwrap().try_collect().await.unwrap();
+    assert_eq!(serde_json::to_value(&streamed).unwrap(), serde_json::to_value(&local).unwrap());
+
+    let local: Vec<_> = client.weapon_configs("M5A3").try_collect().await.unwrap();
+    let streamed: Vec<_> =
+        remote.weapon_configs("M5A3").await.unwrap().try_collect().await.unwrap();
+    assert!(!streamed.is_empty());
+    assert_eq!(serde_json::to_value(&streamed).unwrap(), serde_json::to_value(&local).unwrap());
+
+    let local: Vec<_> = client.damage_at_range("M5A3", 40).try_collect().await.unwrap();
+    let streamed: Vec<_> =
+        remote.damage_at_range("M5A3", 40).await.unwrap().try_collect().await.unwrap();
+    assert_eq!(streamed.len(), local.len());
+    for (remote_row, local_row) in streamed.iter().zip(&local) {
+        assert_eq!(remote_row.barrel_name, local_row.barrel_name);
+        assert_eq!(remote_row.damage, local_row.damage);
+    }
+
+    let local: Vec<_> =
+        client.best_configs_in_category("Assault Rifles", 50, 3).try_collect().await.unwrap();
+    let streamed: Vec<_> = remote
+        .best_configs_in_category("Assault Rifles", 50, 3)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(serde_json::to_value(&streamed).unwrap(), serde_json::to_value(&local).unwrap());
+
+    assert_eq!(remote.weapon("M5A3").await.unwrap().weapon_name, "M5A3");
+    assert!(matches!(
+        remote.weapon("Missing").await,
+        Err(bf2042_stats::StatsError::NotFound(name)) if name == "Weapon 'Missing'"
+    ));
+    assert!(matches!(
+        remote.weapon_configs("Missing").await,
+        Err(bf2042_stats::StatsError::NotFound(_))
+    ));
+
+    // Listings and analyses round-trip to the same JSON as the local client
+    macro_rules! assert_same {
+        ($remote:expr, $local:expr) => {
+            assert_eq!(
+                serde_json::to_value(&$remote).unwrap(),
+                serde_json::to_value(&$local).unwrap()
+            )
+        };
+    }
+
+    let local: Vec<_> = client.barrels().try_collect().await.unwrap();
+    let streamed: Vec<_> = remote.barrels().await.unwrap().try_collect().await.unwrap();
+    assert_same!(streamed, local);
+    let local: Vec<_> = client.ammo_types().try_collect().await.unwrap();
+    let streamed: Vec<_> = remote.ammo_types().await.unwrap().try_collect().await.unwrap();
+    assert_same!(streamed, local);
+    let local: Vec<_> = client.all_weapons().try_collect().await.unwrap();
+    let streamed: Vec<_> = remote.all_weapons().await.unwrap().try_collect().await.unwrap();
+    assert_same!(streamed, local);
+    let local: Vec<_> = client.target_profiles().try_collect().await.unwrap();
+    let streamed: Vec<_> = remote.target_profiles().await.unwrap().try_collect().await.unwrap();
+    assert_same!(streamed, local);
+    assert_eq!(remote.target_profile("Armored").await.unwrap(), TargetProfile::armored());
+
+    let local = client.weapon_details_aggregated("M5A3").await.unwrap();
+    let remote_details = remote.weapon_details_aggregated("M5A3").await.unwrap();
+    assert_same!(remote_details, local);
+
+    let query = bf2042_stats::models::ConfigQuery::new()
+        .with_category("Assault Rifles")
+        .with_max_reload(rust_decimal::Decimal::new(25, 1))
+        .at_range(40)
+        .sort_by(
+            bf2042_stats::models::ConfigSort::FireRate,
+            bf2042_stats::models::SortOrder::Asc,
+        )
+        .with_limit(10);
+    let local: Vec<_> = client.query_configs(&query).try_collect().await.unwrap();
+    let streamed: Vec<_> =
+        remote.query_configs(&query).await.unwrap().try_collect().await.unwrap();
+    assert!(!streamed.is_empty());
+    assert_same!(streamed, local);
+
+    let armored = TargetProfile::armored();
+    let query = bf2042_stats::models::RangeBandQuery::new(25, 100)
+        .with_limit(3)
+        .against(armored.clone());
+    let local: Vec<_> = client.range_band_winners(&query).try_collect().await.unwrap();
+    let streamed: Vec<_> = remote
+        .range_band_winners(&query)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert!(!streamed.is_empty());
+    assert_same!(streamed, local);
+    // The client rejects the bands once the response streams
+    let query = bf2042_stats::models::RangeBandQuery { band_width: 0, ..query };
+    let invalid: bf2042_stats::Result<Vec<_>> = async {
+        remote.range_band_winners(&query).await?.try_collect().await
+    }
+    .await;
+    assert!(matches!(
+        invalid,
+        Err(bf2042_stats::StatsError::QueryFailed(message))
+            if message.contains("Invalid range bands")
+    ));
+
+    let configs = &remote_details.configurations;
+    let (config_a, config_b) = (configs[0].config_id, configs[1].config_id);
+    let ranges = [10, 50, 100];
+    let local = client.compare(config_a, config_b, &ranges, &armored).await.unwrap();
+    let compared = remote.compare(config_a, config_b, &ranges, &armored).await.unwrap();
+    assert_same!(compared, local);
+
+    let metrics = [ParetoMetric::TtkAt(20), ParetoMetric::DamageAt(75), ParetoMetric::FireRate];
+    let local = client.pareto_configs("M5A3", &metrics, &armored).await.unwrap();
+    let frontier = remote.pareto_configs("M5A3", &metrics, &armored).await.unwrap();
+    assert!(!frontier.is_empty());
+    assert_same!(frontier, local);
+    assert!(matches!(
+        remote.pareto_configs("M5A3", &[], &armored).await,
+        Err(bf2042_stats::StatsError::QueryFailed(_))
+    ));
+    assert!(matches!(
+        remote.pareto_configs("Missing", &metrics, &armored).await,
+        Err(bf2042_stats::StatsError::NotFound(_))
+    ));
+}
+
+#[cfg(feature = "mcp")]
+#[tokio::test]
+async fn test_mcp_streamable_http_sessions() {
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use bf2042_stats::render::{self, Table};
+    use futures::TryStreamExt;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio_util::sync::CancellationToken;
+    use tower::ServiceExt;
+
+    let client = Arc::new(setup_test_client("mcp").await.expect("Failed to setup test client"));
+    let shutdown = CancellationToken::new();
+    let app: axum::Router = bf2042_stats::mcp::router(client.clone(), shutdown.clone());
+
+    let post = |session: Option<String>, message: serde_json::Value| {
+        let mut request = Request::post("/mcp")
+            .header("content-type", "application/json")
+            .header("accept", "application/json, text/event-stream");
+        if let Some(session) = session {
+            request = request.header("mcp-session-id", session);
+        }
+        let request = request.body(Body::from(message.to_string())).unwrap();
+        app.clone().oneshot(request)
+    };
+    // JSON-RPC messages carried by the `data:` lines of an SSE body
+    let messages = |response: axum::response::Response| async move {
+        let body = to_bytes(response.into_body(), usize::MAX);
+        let body = tokio::time::timeout(Duration::from_secs(10), body)
+            .await
+            .expect("SSE stream should end after the response")
+            .unwrap();
+        String::from_utf8(body.to_vec())
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .filter(|data| !data.trim().is_empty())
+            .map(|data| serde_json::from_str::<serde_json::Value>(data.trim()).unwrap())
+            .collect::<Vec<_>>()
+    };
+    let initialize = serde_json::json!({
+        "jsonrpc": "2.0", "id": 1, "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": {"name": "test", "version": "0"}
+        }
+    });
+    let initialized = serde_json::json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+
+    // Two concurrent sessions share the client
+    let mut sessions = Vec::new();
+    for _ in 0..2 {
+        let response = post(None, initialize.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = response.headers()["mcp-session-id"].to_str().unwrap().to_string();
+        let init = messages(response).await;
+        assert_eq!(init[0]["result"]["serverInfo"]["name"], "bf2042_stats");
+        let response = post(Some(session.clone()), initialized.clone()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        sessions.push(session);
+    }
+    assert_ne!(sessions[0], sessions[1]);
+
+    let call = |id: u32, name: &str, arguments: serde_json::Value| {
+        serde_json::json!({
+            "jsonrpc": "2.0", "id": id, "method": "tools/call",
+            "params": {"name": name, "arguments": arguments}
+        })
+    };
+
+    let list = serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+    let response = post(Some(sessions[0].clone()), list).await.unwrap();
+    let tools = messages(response).await;
+    let tools = tools[0]["result"]["tools"].as_array().unwrap();
+    assert!(tools.iter().any(|tool| tool["name"] == "damage_at_range"));
+
+    let response = post(
+        Some(sessions[1].clone()),
+        call(3, "damage_at_range", serde_json::json!({"weapon": "M5A3", "range": 40})),
+    )
+    .await
+    .unwrap();
+    let result = &messages(response).await[0]["result"];
+    assert_eq!(result["isError"], false);
+    let rows: serde_json::Value =
+        serde_json::from_str(result["content"][0]["text"].as_str().unwrap()).unwrap();
+    let local: Vec<_> = client.damage_at_range("M5A3", 40).try_collect().await.unwrap();
+    assert_eq!(rows, serde_json::to_value(&local).unwrap());
+
+    let missing = call(4, "weapon_configs", serde_json::json!({"weapon": "Missing"}));
+    let response = post(Some(sessions[0].clone()), missing).await.unwrap();
+    let result = &messages(response).await[0]["result"];
+    assert_eq!(result["isError"], true);
+    assert_eq!(result["content"][0]["text"], "Weapon 'Missing' not found");
+
+    // Tables render with the same renderer as the library
+    let arguments = serde_json::json!({
+        "weapon": "M5A3", "range": 40, "format": "markdown", "columns": ["barrel_name", "damage"],
+    });
+    let response =
+        post(Some(sessions[1].clone()), call(8, "damage_at_range", arguments)).await.unwrap();
+    let result = &messages(response).await[0]["result"];
+    let table = Table::new(&local).with_columns(&["barrel_name", "damage"]).unwrap();
+    assert_eq!(result["content"][0]["text"], render::markdown(&table));
+
+    let arguments =
+        serde_json::json!({"weapon": "M5A3", "range": 40, "format": "html", "columns": ["dps"]});
+    let response =
+        post(Some(sessions[1].clone()), call(9, "damage_at_range", arguments)).await.unwrap();
+    let result = &messages(response).await[0]["result"];
+    assert_eq!(result["isError"], true);
+    assert!(result["content"][0]["text"].as_str().unwrap().starts_with("Unknown column 'dps'"));
+
+    let arguments =
+        serde_json::json!({"weapon": "M5A3", "format": "markdown", "decimal_places": 29});
+    let response =
+        post(Some(sessions[1].clone()), call(10, "weapon_details", arguments)).await.unwrap();
+    let error = &messages(response).await[0]["error"];
+    assert_eq!(error["code"], -32602);
+    assert_eq!(error["message"], "Decimal places must be at most 28, got 29");
+
+    // Cancelling a call blocked in the database ends it and hands its connection back
+    let locker = DatabaseManager::new(&test_db_config("mcp")).await.unwrap();
+    let mut lock = locker.pool().begin().await.unwrap();
+    sqlx::query("LOCK TABLE config_dropoffs IN ACCESS EXCLUSIVE MODE")
+        .execute(&mut *lock)
+        .await
+        .unwrap();
+    let arguments = serde_json::json!({"weapon": "M5A3", "range": 40});
+    let response =
+        post(Some(sessions[1].clone()), call(11, "damage_at_range", arguments)).await.unwrap();
+    let blocked = tokio::spawn(messages(response));
+    let waiting = "SELECT COUNT(*) FROM pg_stat_activity \
+                   WHERE wait_event_type = 'Lock' AND datname = current_database()";
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while sqlx::query_scalar::<_, i64>(waiting).fetch_one(locker.pool()).await.unwrap() == 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Tool call should wait for the table lock");
+    let pool = client.database_manager().pool();
+    assert!(pool.size() > pool.num_idle() as u32);
+
+    let cancelled = serde_json::json!({
+        "jsonrpc": "2.0", "method": "notifications/cancelled",
+        "params": {"requestId": 11, "reason": "test"}
+    });
+    let response = post(Some(sessions[1].clone()), cancelled).await.unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let replies = blocked.await.unwrap();
+    assert!(replies.iter().all(|reply| reply["result"].is_null()));
+
+    // The server side of the aborted query finishes once the lock is gone
+    lock.rollback().await.unwrap();
+    tokio::time::timeout(Duration::from_secs(10), async {
+        while pool.num_idle() as u32 != pool.size() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("Cancelled call should release its connection");
+
+    // A tool call without a session is rejected, as is a closed session
+    let response = post(None, call(5, "list_categories", serde_json::json!({}))).await.unwrap();
+    assert!(response.status().is_client_error());
+    let request = Request::delete("/mcp")
+        .header("mcp-session-id", &sessions[0])
+        .body(Body::empty())
+        .unwrap();
+    assert!(app.clone().oneshot(request).await.unwrap().status().is_success());
+    let list_categories = |id| call(id, "list_categories", serde_json::json!({}));
+    let response = post(Some(sessions[0].clone()), list_categories(6)).await.unwrap();
+    assert!(response.status().is_client_error());
+
+    // The other session is unaffected until shutdown
+    let response = post(Some(sessions[1].clone()), list_categories(7)).await.unwrap();
+    assert_eq!(messages(response).await[0]["result"]["isError"], false);
+    shutdown.cancel();
+}
+
+#[tokio::test]
+async fn test_csv_round_trip() {
+    use bf2042_stats::StatsError;
+    use std::fs;
+
+    let source = setup_test_db("csv_source").await.expect("Failed to setup test database");
+    source.populate_from_embedded_data().await.expect("Failed to populate data");
+    let dir = env::temp_dir().join(format!("bf2042_csv_{}", std::process::id()));
+    let dir_again = dir.join("again");
+    source.export_csv(&dir).await.expect("Failed to export CSV");
+
+    let dropoffs = fs::read_to_string(dir.join("config_dropoffs.csv")).unwrap();
+    assert!(dropoffs.starts_with("config_id,range,damage\n"));
+    // Decimals keep their column scale
+    assert!(dropoffs.lines().nth(1).unwrap().ends_with(".0"));
+
+    // Importing into an empty database and exporting again reproduces every file
+    let target = setup_test_db("csv_target").await.expect("Failed to setup test database");
+    target.import_csv(&dir).await.expect("Failed to import CSV");
+    target.export_csv(&dir_again).await.expect("Failed to export CSV");
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_file() {
+            let again = fs::read(dir_again.join(path.file_name().unwrap())).unwrap();
+            assert_eq!(fs::read(&path).unwrap(), again, "{} differs", path.display());
+        }
+    }
+    assert!(target.validate_data().await.unwrap().is_valid);
+
+    // Sequences continue after the imported ids
+    let profile = TargetProfile {
+        profile_name: "Imported".to_string(),
+        ..TargetProfile::soldier()
+    };
+    target.save_target_profile(&profile).await.expect("Failed to save profile after import");
+
+    // Columns may be reordered, but not missing or unknown
+    let barrels = fs::read_to_string(dir.join("barrels.csv")).unwrap();
+    let reordered: String = barrels
+        .lines()
+        .map(|line| {
+            let (id, name) = line.split_once(',').unwrap();
+            format!("{},{}\n", name, id)
+        })
+        .collect();
+    fs::write(dir.join("barrels.csv"), reordered).unwrap();
+    target.import_csv(&dir).await.expect("Reordered columns should import");
+
+    fs::write(dir.join("barrels.csv"), barrels.replacen("barrel_name", "name", 1)).unwrap();
+    let err = target.import_csv(&dir).await.unwrap_err();
+    assert!(matches!(err, StatsError::InvalidCsv(_)), "{}", err);
+    assert!(err.to_string().contains("barrels.csv has unknown column 'name'"), "{}", err);
+
+    // Invalid values abort the whole import
+    fs::write(dir.join("barrels.csv"), barrels).unwrap();
+    let weapons = fs::read_to_string(dir.join("weapons.csv")).unwrap();
+    fs::write(dir.join("weapons.csv"), weapons.replacen(",1\n", ",999\n", 1)).unwrap();
+    let err = target.import_csv(&dir).await.unwrap_err();
+    assert!(err.to_string().contains("weapons.csv"), "{}", err);
+    let weapon_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM weapons")
+        .fetch_one(target.pool())
+        .await
+        .unwrap();
+    assert_eq!(weapon_count.0, weapons.lines().count() as i64 - 1);
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+/// Drop the whitespace between JSON tokens, keeping it inside strings
+fn strip_json_whitespace(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+    let (mut in_string, mut escaped) = (false, false);
+    for c in json.chars() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c.is_whitespace() {
+            continue;
+        } else {
+            in_string = c == '"';
+        }
+        stripped.push(c);
+    }
+    stripped
+}
+
+#[tokio::test]
+async fn test_weapons_data_round_trip() {
+    use bf2042_stats::models::WeaponsData;
+    use rust_decimal::RoundingStrategy;
+
+    const WEAPONS_JSON: &str = include_str!("../weapons.json");
+
+    let source = setup_test_db("json_source").await.expect("Failed to setup test database");
+    source.populate_from_json_str(WEAPONS_JSON).await.expect("Failed to populate data");
+    let exported = source.export_weapons_data().await.expect("Failed to export weapons data");
+
+    // The document model writes weapons.json back byte for byte, whitespace aside
+    let mut original: WeaponsData = serde_json::from_str(WEAPONS_JSON).unwrap();
+    assert_eq!(serde_json::to_string(&original).unwrap(), strip_json_whitespace(WEAPONS_JSON));
+
+    // Import -> export loses nothing beyond rounding damage to its DECIMAL(5,1) column
+    for weapon in original.categories.iter_mut().flat_map(|c| c.weapons.iter_mut()) {
+        for dropoff in weapon.stats.iter_mut().flat_map(|s| s.dropoffs.iter_mut()) {
+            dropoff.damage = dropoff
+                .damage
+                .round_dp_with_strategy(1, RoundingStrategy::MidpointAwayFromZero)
+                .normalize();
+        }
+    }
+    assert_eq!(
+        serde_json::to_string(&exported).unwrap(),
+        serde_json::to_string(&original).unwrap()
+    );
+
+    // Re-importing the export reproduces it byte for byte
+    let json = serde_json::to_string_pretty(&exported).unwrap();
+    let target = setup_test_db("json_target").await.expect("Failed to setup test database");
+    target.populate_from_json_str(&json).await.expect("Failed to import exported data");
+    let again = target.export_weapons_data().await.expect("Failed to export weapons data");
+    assert_eq!(serde_json::to_string_pretty(&again).unwrap(), json);
+
+    // Database edits show up in the export
+    sqlx::query("UPDATE config_dropoffs SET damage = 99.5 WHERE config_id = 1 AND range = 0")
+        .execute(target.pool())
+        .await
+        .unwrap();
+    let edited = target.export_weapons_data().await.unwrap();
+    let dropoff = &edited.categories[0].weapons[0].stats[0].dropoffs[0];
+    assert_eq!(dropoff.damage.to_string(), "99.5");
+}
+
+#[tokio::test]
+async fn test_render_tables() {
+    use bf2042_stats::render::{self, StatCard, Table};
+    use futures::TryStreamExt;
+
+    let client = setup_test_client("render").await.expect("Failed to setup test client");
+    let rows: Vec<_> = client.damage_at_range("M5A3", 40).try_collect().await.unwrap();
+    assert!(!rows.is_empty());
+
+    // Selected columns in order, text left-aligned and numbers right-aligned
+    let table = Table::new(&rows)
+        .with_columns(&["barrel_name", "damage"])
+        .unwrap()
+        .with_decimal_places(2);
+    let markdown = render::markdown(&table);
+    let lines: Vec<_> = markdown.lines().collect();
+    assert_eq!(lines.len(), rows.len() + 2);
+    assert!(lines[0].starts_with("| Barrel ") && lines[0].ends_with(" Damage |"));
+    assert!(lines[1].starts_with("| ---") && lines[1].ends_with("-: |"));
+    let damage = rows[0].damage.round_dp(2);
+    assert!(lines[2].contains(&rows[0].barrel_name));
+    assert!(lines[2].ends_with(&format!(" {:.2} |", damage)));
+    let width = lines[0].chars().count();
+    assert!(lines.iter().all(|line| line.chars().count() == width));
+
+    let html = render::html(&table);
+    assert!(html.starts_with("<table>\n<thead>\n<tr><th>Barrel</th>"));
+    assert_eq!(html.matches("<tr>").count(), rows.len() + 1);
+    assert!(html.contains(&format!("<td style=\"text-align: right\">{:.2}</td>", damage)));
+
+    // Grouped configs, ammo stats and category results use the same tables
+    let configs: Vec<_> = client.weapon_configs_grouped("M5A3").try_collect().await.unwrap();
+    let markdown = render::markdown(&Table::new(&configs));
+    assert!(markdown.lines().next().unwrap().trim_end_matches([' ', '|']).ends_with("| Damage"));
+    assert!(markdown.contains(&format!(" @ {}m", configs[0].dropoffs[0].range)));
+    let ammo_stats: Vec<_> =
+        configs.iter().filter_map(|config| config.ammo_stats.clone()).collect();
+    assert_eq!(render::markdown(&Table::new(&ammo_stats)).lines().count(), ammo_stats.len() + 2);
+    let best: Vec<_> =
+        client.best_configs_in_category("Assault Rifles", 0, 3).try_collect().await.unwrap();
+    assert_eq!(render::html(&Table::new(&best)).matches("<tr>").count(), best.len() + 1);
+
+    // The stat card summarizes the weapon and lists damage by range
+    let details = client.weapon_details_aggregated("M5A3").await.unwrap();
+    let card = render::markdown(&StatCard::from_details(&details));
+    assert!(card.starts_with(&format!("## M5A3\n\n*{}*\n", details.category_name)));
+    let count = card.lines().find(|line| line.starts_with("| Configurations ")).unwrap();
+    assert!(count.contains(&format!("| {} ", details.configurations.len())));
+    assert!(card.contains("### Damage by range"));
+    let html = render::html(&StatCard::new("M5A3 <SMG>", &configs));
+    assert!(html.starts_with("<section class=\"stat-card\">\n<h2>M5A3 &lt;SMG&gt;</h2>"));
+    assert!(html.ends_with("</section>\n"));
+}
+
+#[cfg(feature = "parquet")]
+#[tokio::test]
+async fn test_damage_table_parquet_export() {
+    use arrow::array::{Array, AsArray};
+    use arrow::datatypes::Decimal128Type;
+    use bf2042_stats::columnar;
+    use futures::TryStreamExt;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let client = setup_test_client("parquet").await.expect("Failed to setup test client");
+    let rows: Vec<_> = client.damage_table().try_collect().await.unwrap();
+    assert!(!rows.is_empty());
+
+    // Batches split the table without losing rows
+    let batches: Vec<_> = columnar::damage_table_batches(&client, 100).try_collect().await.unwrap();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), rows.len());
+    assert!(batches.iter().all(|b| b.num_rows() <= 100));
+
+    let path = env::temp_dir().join(format!("bf2042_damage_{}.parquet", std::process::id()));
+    let file = tokio::fs::File::create(&path).await.unwrap();
+    columnar::write_damage_table_parquet(&client, file).await.expect("Failed to write Parquet");
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches: Vec<_> = reader.collect::<std::result::Result<_, _>>().unwrap();
+    std::fs::remove_file(&path).ok();
+    assert_eq!(batches[0].schema(), columnar::damage_table_schema());
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), rows.len());
+
+    // Decimals keep their exact database value
+    let first = &batches[0];
+    let damage = first.column_by_name("damage").unwrap().as_primitive::<Decimal128Type>();
+    assert_eq!(damage.value_as_string(0), rows[0].damage.to_string());
+    let reload = first
+        .column_by_name("empty_reload_time")
+        .unwrap()
+        .as_primitive::<Decimal128Type>();
+    match rows[0].empty_reload_time {
+        Some(time) => assert_eq!(reload.value_as_string(0), time.to_string()),
+        None => assert!(reload.is_null(0)),
+    }
+    let weapon = first.column_by_name("weapon_name").unwrap().as_string::<i32>();
+    assert_eq!(weapon.value(0), rows[0].weapon_name);
+}